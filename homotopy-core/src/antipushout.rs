@@ -1,342 +1,375 @@
-// use std::ops::Range;
-
-// use itertools::Itertools;
-
-// use crate::{
-//     factorization::factorize, monotone::Monotone, rewrite::Cone, Cospan, Diagram, DiagramN, Height,
-//     Rewrite, Rewrite0, RewriteN,
-// };
-
-// mod monotone {
-//     use itertools::Itertools;
-
-//     use crate::monotone::Monotone;
-
-//     // Given a cospan a -> 1 <- b in ∆, return an antipushout span a <-h- s -k-> b.
-//     pub fn antipushout_base(a: usize, b: usize) -> Vec<(Monotone, Monotone)> {
-//         match (a, b) {
-//             (0, 1) | (1, 0) => vec![(vec![].into(), vec![].into())],
-//             (0, _) | (_, 0) => vec![],
-//             (1, b) => vec![(vec![0; b].into(), (0..b).collect())],
-//             (a, 1) => vec![((0..a).collect(), vec![0; a].into())],
-//             (a, b) => {
-//                 let mut antipushouts = vec![];
-//                 for (mut h, mut k) in antipushout_base(a - 1, b) {
-//                     h.push(a - 1);
-//                     k.push(b - 1);
-//                     antipushouts.push((h, k));
-//                 }
-//                 for (mut h, mut k) in antipushout_base(a, b - 1) {
-//                     h.push(a - 1);
-//                     k.push(b - 1);
-//                     antipushouts.push((h, k));
-//                 }
-//                 antipushouts
-//             }
-//         }
-//     }
-
-//     // Given a cospan a ―f-> t <-g— b in ∆, return an antipushout span a <-h— s —k-> b.
-//     pub fn antipushout(
-//         f: &Monotone,
-//         g: &Monotone,
-//         target_size: usize,
-//     ) -> Vec<(Monotone, Monotone)> {
-//         let f_preimages = (0..target_size).map(|j| f.preimage(j)).collect_vec();
-//         let g_preimages = (0..target_size).map(|j| g.preimage(j)).collect_vec();
-
-//         (0..target_size)
-//             .map(|j| {
-//                 let n = f_preimages[j].len();
-//                 let m = g_preimages[j].len();
-//                 antipushout_base(n, m)
-//             })
-//             .multi_cartesian_product()
-//             .map(|components| {
-//                 let mut h = vec![];
-//                 let mut k = vec![];
-//                 for (j, (h_j, k_j)) in components.into_iter().enumerate() {
-//                     let f_preimage = &f_preimages[j];
-//                     let g_preimage = &g_preimages[j];
-//                     h.extend(h_j.slices().map(|i| i + f_preimage.start));
-//                     k.extend(k_j.slices().map(|i| i + g_preimage.start));
-//                 }
-//                 (h.into(), k.into())
-//             })
-//             .collect()
-//     }
-// }
-
-// /// Given `Rewrite`s A -f> T <g- B, find some `Rewrite`s A <h- S -k> B such that the square is a pushout.
-// #[allow(clippy::many_single_char_names)]
-// pub fn antipushout(
-//     a: &Diagram,
-//     b: &Diagram,
-//     t: &Diagram,
-//     f: &Rewrite,
-//     g: &Rewrite,
-// ) -> Vec<(Diagram, Rewrite, Rewrite)> {
-//     match (a, b, t, f, g) {
-//         (
-//             Diagram::Diagram0(a),
-//             Diagram::Diagram0(b),
-//             Diagram::Diagram0(_),
-//             Rewrite::Rewrite0(_),
-//             Rewrite::Rewrite0(_),
-//         ) => {
-//             if a != b && a.dimension == b.dimension {
-//                 vec![]
-//             } else {
-//                 let s = std::cmp::min_by_key(*a, *b, |g| g.dimension);
-//                 vec![(
-//                     s.into(),
-//                     Rewrite0::new(s, *a, todo!()).into(),
-//                     Rewrite0::new(s, *b, todo!()).into(),
-//                 )]
-//             }
-//         }
-//         (
-//             Diagram::DiagramN(a),
-//             Diagram::DiagramN(b),
-//             Diagram::DiagramN(t),
-//             Rewrite::RewriteN(f),
-//             Rewrite::RewriteN(g),
-//         ) => {
-//             assert_eq!(f.dimension(), a.dimension());
-//             assert_eq!(f.dimension(), t.dimension());
-//             assert_eq!(g.dimension(), b.dimension());
-//             assert_eq!(g.dimension(), t.dimension());
-
-//             let f_mono = f.singular_monotone(a.size());
-//             let g_mono = g.singular_monotone(b.size());
-
-//             monotone::antipushout(&f_mono, &g_mono, t.size())
-//                 .iter()
-//                 .flat_map(|(h_mono, k_mono)| {
-//                     assert_eq!(h_mono.len(), k_mono.len());
-//                     if h_mono.is_empty() {
-//                         // If the source is empty, construct the trivial rewrites.
-//                         let s = t.source().identity();
-
-//                         let h = RewriteN::from_slices_unsafe(
-//                             s.dimension(),
-//                             &[],
-//                             a.cospans(),
-//                             todo!(),
-//                             vec![vec![]; a.size()],
-//                         );
-
-//                         let k = RewriteN::from_slices_unsafe(
-//                             s.dimension(),
-//                             &[],
-//                             b.cospans(),
-//                             todo!(),
-//                             vec![vec![]; b.size()],
-//                         );
-
-//                         vec![(s.into(), h.into(), k.into())]
-//                     } else {
-//                         std::iter::zip(h_mono.slices(), k_mono.slices())
-//                             .map(|(ai, bi)| {
-//                                 assert_eq!(f_mono[ai], g_mono[bi]);
-//                                 antipushout(
-//                                     &a.slice(Height::Singular(ai)).unwrap(),
-//                                     &b.slice(Height::Singular(bi)).unwrap(),
-//                                     &t.slice(Height::Singular(f_mono[ai])).unwrap(),
-//                                     &f.slice(ai),
-//                                     &g.slice(bi),
-//                                 )
-//                             })
-//                             .multi_cartesian_product()
-//                             .map(|spans| {
-//                                 let s_slices =
-//                                     spans.iter().map(|span| span.0.clone()).collect_vec();
-//                                 let h_slices =
-//                                     spans.iter().map(|span| span.1.clone()).collect_vec();
-//                                 let k_slices =
-//                                     spans.iter().map(|span| span.2.clone()).collect_vec();
-
-//                                 let s = construct_source(a, h_mono, &h_slices, &s_slices);
-
-//                                 let h: RewriteN = todo!();
-//                                 let k: RewriteN = todo!();
-
-//                                 (s.into(), h.into(), k.into())
-//                             })
-//                             .collect_vec()
-//                     }
-//                 })
-//                 .collect_vec()
-//         }
-//         _ => panic!("Mismatched dimensions"),
-//     }
-// }
-
-// fn construct_source(
-//     target: &DiagramN,
-//     mono: &Monotone,
-//     slices: &[Rewrite],
-//     source_slices: &[Diagram],
-// ) -> DiagramN {
-//     let mut cospans = vec![];
-//     let target_slices = target.singular_slices();
-//     for (ti, cospan) in target.cospans().iter().enumerate() {
-//         let preimage = mono.preimage(ti);
-//         if !preimage.is_empty() {
-//             let start = preimage.start;
-//             let end = preimage.end;
-
-//             let mut rewrites = vec![factorize(
-//                 cospan.forward.clone(),
-//                 slices[start].clone(),
-//                 target_slices[ti].clone(),
-//             )
-//             .next()
-//             .unwrap()];
-
-//             for si in start..end - 1 {
-//                 let span = &antipushout(
-//                     &source_slices[si],
-//                     &source_slices[si + 1],
-//                     &target_slices[ti],
-//                     &slices[si],
-//                     &slices[si + 1],
-//                 )[0];
-//                 rewrites.push(span.1.clone());
-//                 rewrites.push(span.2.clone());
-//             }
-
-//             rewrites.push(
-//                 factorize(
-//                     cospan.backward.clone(),
-//                     slices[end - 1].clone(),
-//                     target_slices[ti].clone(),
-//                 )
-//                 .next()
-//                 .unwrap(),
-//             );
-
-//             for chunk in rewrites.chunks(2) {
-//                 cospans.push(Cospan {
-//                     forward: chunk[0].clone(),
-//                     backward: chunk[1].clone(),
-//                 });
-//             }
-//         }
-//     }
-
-//     DiagramN::new_unsafe(target.source(), cospans)
-// }
-
-// pub fn factorize_inc(source: &Diagram, target: &Diagram, rewrite: &Rewrite) -> (Rewrite, Rewrite) {
-//     let (x, y) = factorize_inc_helper(target, &[(source.clone(), rewrite.clone())]);
-//     (x[0].clone(), y)
-// }
-
-// fn factorize_inc_helper(
-//     target: &Diagram,
-//     incoming: &[(Diagram, Rewrite)],
-// ) -> (Vec<Rewrite>, Rewrite) {
-//     match target {
-//         Diagram::Diagram0(t) => {
-//             let sources = incoming
-//                 .iter()
-//                 .map(|(s, _)| s.max_generator())
-//                 .collect_vec();
-//             let &m = sources.iter().max_by_key(|g| g.dimension).unwrap();
-
-//             (
-//                 sources
-//                     .into_iter()
-//                     .map(|s| Rewrite::from(Rewrite0::new(s, m, todo!())))
-//                     .collect_vec(),
-//                 Rewrite::from(Rewrite0::new(m, *t, todo!())),
-//             )
-//         }
-//         Diagram::DiagramN(target) => {
-//             let mut p_cones = vec![vec![]; incoming.len()];
-//             let mut q_cones = vec![];
-
-//             for ti in 0..target.size() {
-//                 let target_slice = target.slice(Height::Singular(ti)).unwrap();
-
-//                 // Recursively factorise the slices into ti.
-//                 let (mut ps, q) = {
-//                     let mut incoming_slices = Vec::default();
-//                     for (source, rewrite) in incoming {
-//                         let source: &DiagramN = source.try_into().unwrap();
-//                         let rewrite: &RewriteN = rewrite.try_into().unwrap();
-//                         for si in rewrite.singular_preimage(ti) {
-//                             incoming_slices.push((
-//                                 source.slice(Height::Singular(si)).unwrap(),
-//                                 rewrite.slice(si),
-//                             ));
-//                         }
-//                     }
-//                     factorize_inc_helper(&target_slice, &incoming_slices)
-//                 };
-
-//                 let target_cospan = target.cospans()[ti].clone();
-
-//                 let middle_cospan = {
-//                     let forward = factorize(
-//                         target_cospan.forward.clone(),
-//                         q.clone(),
-//                         target_slice.clone(),
-//                     )
-//                     .next()
-//                     .unwrap();
-//                     let backward = factorize(
-//                         target_cospan.backward.clone(),
-//                         q.clone(),
-//                         target_slice.clone(),
-//                     )
-//                     .next()
-//                     .unwrap();
-//                     Cospan { forward, backward }
-//                 };
-
-//                 for (index, (source, rewrite)) in incoming.iter().enumerate() {
-//                     let source: &DiagramN = source.try_into().unwrap();
-//                     let rewrite: &RewriteN = rewrite.try_into().unwrap();
-
-//                     let mut source_cospans = Vec::default();
-//                     let Range { start, end } = rewrite.singular_preimage(ti);
-//                     for si in start..end {
-//                         source_cospans.push(source.cospans()[si].clone());
-//                     }
-
-//                     p_cones[index].push({
-//                         let mut slices = Vec::default();
-//                         for _ in start..end {
-//                             slices.push(ps.remove(0));
-//                         }
-//                         Cone::new(
-//                             start,
-//                             source_cospans,
-//                             middle_cospan.clone(),
-//                             todo!(),
-//                             slices,
-//                         )
-//                     });
-//                 }
-
-//                 q_cones.push(Cone::new(
-//                     ti,
-//                     vec![middle_cospan],
-//                     target_cospan,
-//                     todo!(),
-//                     vec![q],
-//                 ));
-//             }
-
-//             (
-//                 p_cones
-//                     .into_iter()
-//                     .map(|cones| RewriteN::new_unsafe(target.dimension(), cones).into())
-//                     .collect_vec(),
-//                 RewriteN::new_unsafe(target.dimension(), q_cones).into(),
-//             )
-//         }
-//     }
-// }
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    colimit::pushout,
+    common::Mode,
+    factorization::factorize,
+    monotone::{Monotone, Split},
+    rewrite::{CompositionError, Cone},
+    Cospan, Diagram, DiagramN, Rewrite, RewriteN,
+};
+
+#[derive(Debug, Error)]
+pub enum AntipushoutError {
+    #[error("the cospan is of mismatched dimensions")]
+    Dimension,
+
+    #[error("the legs of the cospan map outside of its target")]
+    OutOfRange,
+
+    #[error("the slices of a leg do not compose: {0}")]
+    Composition(#[from] CompositionError),
+
+    #[error("the rewrite does not factor through its image")]
+    Factorization,
+}
+
+pub mod monotone {
+    use itertools::Itertools;
+
+    use super::AntipushoutError;
+    use crate::monotone::{Monotone, MonotoneIterator};
+
+    /// Given a cospan `a -> 1 <- b` in Δ, return every antipushout span `a <-h- s -k-> b`.
+    ///
+    /// These are the lattice paths from `(0, 0)` to `(a - 1, b - 1)`: every step advances exactly
+    /// one of `h` and `k`, so that the span is connected and its pushout is `1`.
+    #[allow(clippy::many_single_char_names)]
+    pub fn antipushout_base(a: usize, b: usize) -> Vec<(Monotone, Monotone)> {
+        match (a, b) {
+            (0, 1) | (1, 0) => vec![(vec![].into(), vec![].into())],
+            (0, _) | (_, 0) => vec![],
+            (a, b) => {
+                // Choose which of the steps advance h; the others advance k.
+                let steps = a + b - 2;
+                MonotoneIterator::new(true, &vec![0..steps; a - 1])
+                    .map(|h_steps| {
+                        let mut h_steps = h_steps.slices().peekable();
+                        let mut h: Monotone = vec![0].into();
+                        let mut k: Monotone = vec![0].into();
+                        let (mut i, mut j) = (0, 0);
+                        for step in 0..steps {
+                            if h_steps.next_if_eq(&step).is_some() {
+                                i += 1;
+                            } else {
+                                j += 1;
+                            }
+                            h.push(i);
+                            k.push(j);
+                        }
+                        (h, k)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Given a cospan `a -f-> t <-g- b` in Δ, return every antipushout span `a <-h- s -k-> b`.
+    pub fn antipushout(
+        f: &Monotone,
+        g: &Monotone,
+        target_size: usize,
+    ) -> Result<Vec<(Monotone, Monotone)>, AntipushoutError> {
+        if f.slices().chain(g.slices()).any(|j| j >= target_size) {
+            return Err(AntipushoutError::OutOfRange);
+        }
+
+        if target_size == 0 {
+            return Ok(vec![(vec![].into(), vec![].into())]);
+        }
+
+        let f_preimages = (0..target_size).map(|j| f.preimage(j)).collect_vec();
+        let g_preimages = (0..target_size).map(|j| g.preimage(j)).collect_vec();
+
+        Ok((0..target_size)
+            .map(|j| antipushout_base(f_preimages[j].len(), g_preimages[j].len()))
+            .multi_cartesian_product()
+            .map(|components| {
+                let mut h = vec![];
+                let mut k = vec![];
+                for (j, (h_j, k_j)) in components.into_iter().enumerate() {
+                    h.extend(h_j.slices().map(|i| i + f_preimages[j].start));
+                    k.extend(k_j.slices().map(|i| i + g_preimages[j].start));
+                }
+                (h.into(), k.into())
+            })
+            .collect())
+    }
+}
+
+/// Given `Rewrite`s `A -f-> T <-g- B`, find `Rewrite`s `A <-h- S -k-> B` such that the square is a
+/// pushout.
+///
+/// Every span returned is checked to be an antipushout. The search is not exhaustive: the
+/// cospans of `S` are obtained by factorising the cospans of `A` and `B`, so only spans whose
+/// regular slices all come from `A` or `B` are found.
+///
+/// Fails if the diagrams and rewrites are not of the same dimension, or if the rewrites map outside
+/// of `T`.
+#[allow(clippy::many_single_char_names)]
+pub fn antipushout(
+    a: &Diagram,
+    b: &Diagram,
+    t: &Diagram,
+    f: &Rewrite,
+    g: &Rewrite,
+) -> Result<Vec<(Diagram, Rewrite, Rewrite)>, AntipushoutError> {
+    let mut spans: Vec<(Diagram, Rewrite, Rewrite)> = vec![];
+
+    let candidates = match (a, b, t, f, g) {
+        (
+            Diagram::Diagram0(a0),
+            Diagram::Diagram0(b0),
+            Diagram::Diagram0(t0),
+            Rewrite::Rewrite0(_),
+            Rewrite::Rewrite0(_),
+        ) => {
+            // The pushout of 0-diagrams is the one of highest dimension, so one side must
+            // already be the target, and the other side is then the apex of the span.
+            let mut candidates = vec![];
+            if b0 == t0 && a0.generator.dimension <= b0.generator.dimension {
+                candidates.push((a.clone(), Rewrite::identity(0), f.clone()));
+            }
+            if a0 == t0 && b0.generator.dimension <= a0.generator.dimension {
+                candidates.push((b.clone(), g.clone(), Rewrite::identity(0)));
+            }
+            candidates
+        }
+        (
+            Diagram::DiagramN(a),
+            Diagram::DiagramN(b),
+            Diagram::DiagramN(t),
+            Rewrite::RewriteN(f),
+            Rewrite::RewriteN(g),
+        ) => {
+            if [f.dimension(), g.dimension(), a.dimension(), b.dimension()]
+                .into_iter()
+                .any(|dimension| dimension != t.dimension())
+            {
+                return Err(AntipushoutError::Dimension);
+            }
+
+            let a_slices = a.singular_slices().collect_vec();
+            let b_slices = b.singular_slices().collect_vec();
+            let t_slices = t.singular_slices().collect_vec();
+
+            let f_mono = f.singular_monotone(a.size());
+            let g_mono = g.singular_monotone(b.size());
+
+            let mut candidates = vec![];
+            for (h_mono, k_mono) in monotone::antipushout(&f_mono, &g_mono, t.size())? {
+                let slice_spans = std::iter::zip(h_mono.slices(), k_mono.slices())
+                    .map(|(ai, bi)| {
+                        antipushout(
+                            &a_slices[ai],
+                            &b_slices[bi],
+                            &t_slices[f_mono[ai]],
+                            &f.slice(ai),
+                            &g.slice(bi),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let combinations: Vec<Vec<(Diagram, Rewrite, Rewrite)>> = if h_mono.is_empty() {
+                    vec![vec![]]
+                } else {
+                    slice_spans.into_iter().multi_cartesian_product().collect()
+                };
+
+                for slices in combinations {
+                    if let Some((s, h, k)) = construct_span(a, b, &h_mono, &k_mono, &slices)? {
+                        candidates.push((s.into(), h.into(), k.into()));
+                    }
+                }
+            }
+            candidates
+        }
+        _ => return Err(AntipushoutError::Dimension),
+    };
+
+    for (s, h, k) in candidates {
+        if !spans.contains(&(s.clone(), h.clone(), k.clone()))
+            && is_antipushout(a, b, t, f, g, &s, &h, &k)
+        {
+            spans.push((s, h, k));
+        }
+    }
+
+    Ok(spans)
+}
+
+/// Assemble the apex of a span and its legs from the monotone maps on singular heights and the
+/// spans between the singular slices.
+#[allow(clippy::many_single_char_names)]
+fn construct_span(
+    a: &DiagramN,
+    b: &DiagramN,
+    h_mono: &Monotone,
+    k_mono: &Monotone,
+    slices: &[(Diagram, Rewrite, Rewrite)],
+) -> Result<Option<(DiagramN, RewriteN, RewriteN)>, AntipushoutError> {
+    let size = slices.len();
+    let a_slices = a.singular_slices().collect_vec();
+    let b_slices = b.singular_slices().collect_vec();
+
+    // Each regular height of S is a regular height of A wherever h changes, and of B wherever k
+    // changes, so the cospans of S are factorisations of the cospans of A or B.
+    let mut cospans = Vec::with_capacity(size);
+    for (i, (_, h_slice, k_slice)) in slices.iter().enumerate() {
+        let (ai, bi) = (h_mono[i], k_mono[i]);
+        let a_cospan = &a.cospans()[ai];
+        let b_cospan = &b.cospans()[bi];
+
+        let factorize_a = |rewrite: &Rewrite| {
+            factorize(rewrite.clone(), h_slice.clone(), a_slices[ai].clone()).next()
+        };
+        let factorize_b = |rewrite: &Rewrite| {
+            factorize(rewrite.clone(), k_slice.clone(), b_slices[bi].clone()).next()
+        };
+
+        let forward = [
+            (i == 0 || h_mono[i - 1] != ai)
+                .then(|| factorize_a(&a_cospan.forward))
+                .flatten(),
+            (i == 0 || k_mono[i - 1] != bi)
+                .then(|| factorize_b(&b_cospan.forward))
+                .flatten(),
+        ]
+        .into_iter()
+        .flatten()
+        .next();
+
+        let backward = [
+            (i == size - 1 || h_mono[i + 1] != ai)
+                .then(|| factorize_a(&a_cospan.backward))
+                .flatten(),
+            (i == size - 1 || k_mono[i + 1] != bi)
+                .then(|| factorize_b(&b_cospan.backward))
+                .flatten(),
+        ]
+        .into_iter()
+        .flatten()
+        .next();
+
+        let (Some(forward), Some(backward)) = (forward, backward) else {
+            return Ok(None);
+        };
+        cospans.push(Cospan { forward, backward });
+    }
+
+    let s = DiagramN::new_unsafe(a.source(), cospans);
+    let h = construct_leg(&s, a, h_mono, slices.iter().map(|(_, h, _)| h.clone()))?;
+    let k = construct_leg(&s, b, k_mono, slices.iter().map(|(_, _, k)| k.clone()))?;
+
+    Ok(Some((s, h, k)))
+}
+
+/// Assemble a rewrite from the monotone map on singular heights and its singular slices.
+///
+/// Fails if the singular slices do not compose with the cospans of the source.
+fn construct_leg(
+    source: &DiagramN,
+    target: &DiagramN,
+    mono: &Monotone,
+    slices: impl Iterator<Item = Rewrite>,
+) -> Result<RewriteN, CompositionError> {
+    let slices = slices.collect_vec();
+    let cones = mono
+        .cones(target.size())
+        .map(
+            |Split {
+                 source: range,
+                 target: j,
+             }| {
+                let target_cospan = target.cospans()[j].clone();
+                let regular_slices = std::iter::once(Ok(target_cospan.forward.clone()))
+                    .chain(
+                        (range.start + 1..range.end)
+                            .map(|i| source.cospans()[i].forward.compose(&slices[i])),
+                    )
+                    .chain((!range.is_empty()).then(|| Ok(target_cospan.backward.clone())))
+                    .collect::<Result<_, _>>()?;
+                Ok(Cone::new(
+                    range.start,
+                    source.cospans()[range.clone()].to_vec(),
+                    target_cospan,
+                    regular_slices,
+                    slices[range].to_vec(),
+                ))
+            },
+        )
+        .collect::<Result<_, _>>()?;
+
+    Ok(RewriteN::new_unsafe(source.dimension(), cones))
+}
+
+/// Factorise a rewrite `f: S -> T` as `S -p-> M -q-> T`, where `q` is an inclusion and `p` hits
+/// every singular height of `M`.
+///
+/// The singular heights of `T` outside the image of `f` are inserted by cones without a source,
+/// so `M` is `T` without them and `q` consists of exactly those cones. Returns `(p, q)`.
+pub fn factorize_inc(
+    source: &Diagram,
+    target: &Diagram,
+    rewrite: &Rewrite,
+) -> Result<(Rewrite, Rewrite), AntipushoutError> {
+    match (source, target, rewrite) {
+        (Diagram::Diagram0(_), Diagram::Diagram0(_), Rewrite::Rewrite0(_)) => {
+            Ok((rewrite.clone(), Rewrite::identity(0)))
+        }
+        (Diagram::DiagramN(s), Diagram::DiagramN(t), Rewrite::RewriteN(f)) => {
+            if s.dimension() != t.dimension() || f.dimension() != t.dimension() {
+                return Err(AntipushoutError::Dimension);
+            }
+
+            // Every singular height of M is hit, so the inserted cones are indexed by the number
+            // of hit heights below them.
+            let mut inserted = vec![];
+            let mut height = 0;
+            for j in 0..t.size() {
+                match f.cone_over_target(j) {
+                    Some(cone) if cone.is_unit() => inserted.push(Cone {
+                        index: height,
+                        ..cone.clone()
+                    }),
+                    _ => height += 1,
+                }
+            }
+
+            let inclusion: Rewrite = RewriteN::new(t.dimension(), inserted).into();
+            let surjection = factorize(rewrite.clone(), inclusion.clone(), target.clone())
+                .next()
+                .ok_or(AntipushoutError::Factorization)?;
+
+            Ok((surjection, inclusion))
+        }
+        _ => Err(AntipushoutError::Dimension),
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::many_single_char_names)]
+fn is_antipushout(
+    a: &Diagram,
+    b: &Diagram,
+    t: &Diagram,
+    f: &Rewrite,
+    g: &Rewrite,
+    s: &Diagram,
+    h: &Rewrite,
+    k: &Rewrite,
+) -> bool {
+    if s.check(Mode::Shallow).is_err()
+        || h.check(Mode::Shallow).is_err()
+        || k.check(Mode::Shallow).is_err()
+    {
+        return false;
+    }
+
+    match pushout(a, b, s, h, k) {
+        Ok((colimit, f_leg, g_leg)) => {
+            colimit == *t && f_leg.equals_modulo_labels(f) && g_leg.equals_modulo_labels(g)
+        }
+        Err(_) => false,
+    }
+}
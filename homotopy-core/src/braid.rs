@@ -0,0 +1,156 @@
+//! Braids built from words in the Artin generators of the braid group.
+//!
+//! The strands of a braid are copies of a 2-dimensional diagram, such as a scalar, stacked on top
+//! of each other. In the 3-dimensional braid, the generator `i` swaps the strands at positions
+//! `i - 1` and `i`, and `-i` is its inverse. The crossings are either given by a braiding
+//! generator chosen by the user, or built out of interchangers, in which case the lower strand
+//! passes in front, as read off by [`crate::invariants::Invariants::braid_word`].
+use std::collections::hash_map::Entry;
+
+use homotopy_common::hash::FastHashMap;
+use thiserror::Error;
+
+use crate::{
+    common::SingularHeight, contraction::ContractionError, diagram::AttachmentError,
+    signature::Signature, Bias, Boundary, Cospan, DiagramN, Direction, Rewrite,
+};
+
+#[derive(Debug, Error)]
+pub enum BraidError {
+    #[error("invalid braid generator: {0}")]
+    Parse(String),
+
+    #[error("a braid needs at least one strand")]
+    NoStrands,
+
+    #[error("the braid generator {0} does not act on {1} strands")]
+    Strands(isize, usize),
+
+    #[error("the braiding does not go from two strands to two strands")]
+    Braiding,
+
+    #[error("failed to stack the strands: {0}")]
+    Attachment(#[from] AttachmentError),
+
+    #[error("failed to braid the strands: {0}")]
+    Contraction(#[from] ContractionError),
+}
+
+/// Parse a braid word written as a sequence of generators such as `s1 s2^-1 s1`, where each
+/// generator may be raised to an integer power.
+pub fn parse_word(word: &str) -> Result<Vec<isize>, BraidError> {
+    let mut result = vec![];
+    for token in word.split_whitespace() {
+        let invalid = || BraidError::Parse(token.to_owned());
+        let (generator, power) = token.split_once('^').unwrap_or((token, "1"));
+        let generator: isize = generator
+            .strip_prefix('s')
+            .and_then(|i| i.parse().ok())
+            .filter(|i| *i > 0)
+            .ok_or_else(invalid)?;
+        let power: isize = power.parse().ok().ok_or_else(invalid)?;
+        for _ in 0..power.unsigned_abs() {
+            result.push(generator * power.signum());
+        }
+    }
+    Ok(result)
+}
+
+/// Construct the 3-dimensional diagram of a braid on a number of copies of `strand`.
+///
+/// Each crossing is an instance of `braiding`, or of its inverse for negative generators, which
+/// must go from two stacked strands to two stacked strands. If no braiding is given, the crossings
+/// are built out of interchangers with [`crossing`].
+pub fn braid(
+    strand: &DiagramN,
+    strands: usize,
+    word: &[isize],
+    braiding: Option<&DiagramN>,
+    signature: &impl Signature,
+) -> Result<DiagramN, BraidError> {
+    if strands == 0 {
+        return Err(BraidError::NoStrands);
+    }
+
+    if let Some(braiding) = braiding {
+        let pair = strand.attach(strand, Boundary::Target, &[])?;
+        if braiding.source() != pair.clone().into() || braiding.target() != pair.into() {
+            return Err(BraidError::Braiding);
+        }
+    }
+
+    let mut slice = strand.clone();
+    for _ in 1..strands {
+        slice = slice.attach(strand, Boundary::Target, &[])?;
+    }
+
+    let mut crossings: FastHashMap<isize, Cospan> = FastHashMap::default();
+    let mut cospans = vec![];
+    for &generator in word {
+        let i = generator.unsigned_abs();
+        if i == 0 || i >= strands {
+            return Err(BraidError::Strands(generator, strands));
+        }
+
+        if let Entry::Vacant(entry) = crossings.entry(generator) {
+            let cospan = match braiding {
+                Some(braiding) => {
+                    let braiding = if generator > 0 {
+                        braiding.clone()
+                    } else {
+                        braiding.inverse()
+                    };
+                    let attached =
+                        slice
+                            .clone()
+                            .identity()
+                            .attach(&braiding, Boundary::Target, &[i - 1])?;
+                    attached.cospans()[0].clone()
+                }
+                None => crossing(&slice, i - 1, generator > 0, signature)?,
+            };
+            entry.insert(cospan);
+        }
+        cospans.push(crossings[&generator].clone());
+    }
+
+    Ok(DiagramN::new(slice.into(), cospans))
+}
+
+/// The cospan in which the strands of a 2-dimensional diagram at heights `height` and
+/// `height + 1` swap places, with the lower one passing in front if `positive` is set. The strands
+/// must be scalars, so that they can be stacked in either order.
+pub fn crossing(
+    slice: &DiagramN,
+    height: SingularHeight,
+    positive: bool,
+    signature: &impl Signature,
+) -> Result<Cospan, ContractionError> {
+    let mut cospans = slice.cospans().to_vec();
+    cospans.swap(height, height + 1);
+    let swapped = DiagramN::new(slice.source(), cospans);
+
+    // Both sides meet in the diagram where the strands are side by side, with the one that starts
+    // out lower in front if the crossing is positive.
+    let contract = |diagram: &DiagramN, bias| -> Result<Rewrite, ContractionError> {
+        let contracted = diagram.clone().identity().contract(
+            Boundary::Target.into(),
+            &mut [],
+            height,
+            Direction::Forward,
+            Some(bias),
+            signature,
+        )?;
+        Ok(contracted.cospans()[0].forward.clone())
+    };
+    let (forward, backward) = if positive {
+        (Bias::Lower, Bias::Higher)
+    } else {
+        (Bias::Higher, Bias::Lower)
+    };
+
+    Ok(Cospan {
+        forward: contract(slice, forward)?,
+        backward: contract(&swapped, backward)?,
+    })
+}
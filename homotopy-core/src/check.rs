@@ -1,4 +1,7 @@
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    hash::{Hash, Hasher},
+};
 
 use homotopy_common::hash::FastHashMap;
 use thiserror::Error;
@@ -314,6 +317,55 @@ pub enum MalformedCone {
     NotCommutative(usize),
 }
 
+impl Diagram {
+    #[must_use]
+    pub fn equals_modulo_labels(&self, other: &Diagram) -> bool {
+        match (self, other) {
+            (Diagram::Diagram0(d), Diagram::Diagram0(e)) => d == e,
+            (Diagram::DiagramN(d), Diagram::DiagramN(e)) => d.equals_modulo_labels(e),
+            (_, _) => false,
+        }
+    }
+
+    /// Hash the diagram consistently with [`Diagram::equals_modulo_labels`].
+    pub fn hash_modulo_labels<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Diagram::Diagram0(d) => {
+                state.write_u8(0);
+                d.hash(state);
+            }
+            Diagram::DiagramN(d) => {
+                state.write_u8(1);
+                d.hash_modulo_labels(state);
+            }
+        }
+    }
+}
+
+impl DiagramN {
+    #[must_use]
+    pub fn equals_modulo_labels(&self, other: &DiagramN) -> bool {
+        self == other
+            || (self.size() == other.size()
+                && self.source().equals_modulo_labels(&other.source())
+                && self
+                    .cospans()
+                    .iter()
+                    .zip(other.cospans())
+                    .all(|(sc, oc)| sc.equals_modulo_labels(oc)))
+    }
+
+    /// Hash the diagram consistently with [`DiagramN::equals_modulo_labels`].
+    pub fn hash_modulo_labels<H: Hasher>(&self, state: &mut H) {
+        self.dimension().hash(state);
+        self.size().hash(state);
+        self.source().hash_modulo_labels(state);
+        for cospan in self.cospans() {
+            cospan.hash_modulo_labels(state);
+        }
+    }
+}
+
 impl Rewrite {
     #[must_use]
     pub fn equals_modulo_labels(&self, other: &Rewrite) -> bool {
@@ -324,6 +376,20 @@ impl Rewrite {
             (_, _) => false,
         }
     }
+
+    /// Hash the rewrite consistently with [`Rewrite::equals_modulo_labels`].
+    pub fn hash_modulo_labels<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Rewrite::Rewrite0(f) => {
+                state.write_u8(0);
+                f.hash_modulo_labels(state);
+            }
+            Rewrite::RewriteN(f) => {
+                state.write_u8(1);
+                f.hash_modulo_labels(state);
+            }
+        }
+    }
 }
 
 impl Cospan {
@@ -332,6 +398,12 @@ impl Cospan {
         self.forward.equals_modulo_labels(&other.forward)
             && self.backward.equals_modulo_labels(&other.backward)
     }
+
+    /// Hash the cospan consistently with [`Cospan::equals_modulo_labels`].
+    pub fn hash_modulo_labels<H: Hasher>(&self, state: &mut H) {
+        self.forward.hash_modulo_labels(state);
+        self.backward.hash_modulo_labels(state);
+    }
 }
 
 impl Rewrite0 {
@@ -343,6 +415,12 @@ impl Rewrite0 {
             (_, _) => false,
         }
     }
+
+    /// Hash the rewrite consistently with [`Rewrite0::equals_modulo_labels`].
+    pub fn hash_modulo_labels<H: Hasher>(&self, state: &mut H) {
+        self.source().hash(state);
+        self.target().hash(state);
+    }
 }
 
 impl RewriteN {
@@ -389,4 +467,48 @@ impl RewriteN {
                             .all(|(scc, occ)| scc.equals_modulo_labels(occ))
                 })
     }
+
+    /// Hash the rewrite consistently with [`RewriteN::equals_modulo_labels`].
+    pub fn hash_modulo_labels<H: Hasher>(&self, state: &mut H) {
+        self.dimension().hash(state);
+        self.cones().len().hash(state);
+        for cone in self.cones() {
+            cone.index.hash(state);
+            cone.len().hash(state);
+            cone.target().hash_modulo_labels(state);
+            for cospan in cone.source() {
+                cospan.hash_modulo_labels(state);
+            }
+            for slice in cone.regular_slices().iter().chain(cone.singular_slices()) {
+                slice.hash_modulo_labels(state);
+            }
+        }
+    }
 }
+
+/// Wrapper comparing and hashing a diagram or rewrite modulo labels, so that it can be used as the
+/// key of a map or set.
+#[derive(Clone, Debug)]
+pub struct ModuloLabels<T>(pub T);
+
+macro_rules! impl_modulo_labels {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq for ModuloLabels<$t> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.0.equals_modulo_labels(&other.0)
+                }
+            }
+
+            impl Eq for ModuloLabels<$t> {}
+
+            impl Hash for ModuloLabels<$t> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.hash_modulo_labels(state);
+                }
+            }
+        )*
+    };
+}
+
+impl_modulo_labels!(Diagram, DiagramN, Rewrite, RewriteN, Cospan);
@@ -0,0 +1,140 @@
+use homotopy_common::idx::IdxVec;
+use itertools::Itertools;
+use petgraph::{
+    algo::{connected_components, is_cyclic_directed},
+    graph::{DefaultIx, DiGraph, IndexType, NodeIndex},
+    visit::EdgeRef,
+};
+use thiserror::Error;
+
+use crate::{
+    common::Height,
+    contraction::{self, ContractGraph, ContractNode, ContractionError},
+    scaffold::{Scaffold, ScaffoldEdge, ScaffoldNode},
+    Diagram, Rewrite,
+};
+
+/// The colimit of a graph of diagrams and rewrites, together with a leg into it from each node
+/// of the graph.
+#[derive(Debug, Clone)]
+pub struct Cocone<Ix = DefaultIx>
+where
+    Ix: IndexType,
+{
+    pub colimit: Diagram,
+    pub legs: IdxVec<NodeIndex<Ix>, Rewrite>,
+}
+
+#[derive(Debug, Error)]
+pub enum ColimitError {
+    #[error("the graph is empty")]
+    Empty,
+
+    #[error("the graph is not connected")]
+    Disconnected,
+
+    #[error("the graph has a cycle")]
+    Cyclic,
+
+    #[error("the diagrams and rewrites in the graph are not all of the same dimension")]
+    Dimension,
+
+    #[error("the rewrite on edge {0} does not go between the diagrams it connects")]
+    Malformed(usize),
+
+    #[error("the coordinates of the nodes are not distinct and of the same length")]
+    Coordinates,
+
+    #[error("the graph has no colimit: {0}")]
+    NoColimit(#[from] ContractionError),
+}
+
+/// Compute the colimit of a finite, connected and acyclic graph of diagrams and rewrites of the
+/// same dimension.
+///
+/// The key of each node is its coordinate, i.e. the heights at which it sits in the slices of a
+/// larger diagram, such as `[Regular(0)]`, `[Singular(0)]` and `[Regular(1)]` for the nodes of a
+/// cospan. Coordinates must be distinct and of the same length. They decide in which order the
+/// graph is collapsed, and over which slices the orientations of cells must agree. The keys of
+/// edges are ignored. The legs of the cocone are indexed by the nodes of the graph.
+pub fn colimit<E, Ix: IndexType>(
+    graph: &Scaffold<Vec<Height>, E, Ix>,
+) -> Result<Cocone<Ix>, ColimitError> {
+    let dimension = graph
+        .node_weights()
+        .next()
+        .ok_or(ColimitError::Empty)?
+        .diagram
+        .dimension();
+
+    if graph
+        .node_weights()
+        .any(|node| node.diagram.dimension() != dimension)
+        || graph
+            .edge_weights()
+            .any(|edge| edge.rewrite.dimension() != dimension)
+    {
+        return Err(ColimitError::Dimension);
+    }
+
+    let length = graph.node_weights().next().map_or(0, |node| node.key.len());
+    if graph.node_weights().any(|node| node.key.len() != length)
+        || !graph.node_weights().map(|node| &node.key).all_unique()
+    {
+        return Err(ColimitError::Coordinates);
+    }
+
+    if connected_components(graph) > 1 {
+        return Err(ColimitError::Disconnected);
+    }
+
+    if is_cyclic_directed(graph) {
+        return Err(ColimitError::Cyclic);
+    }
+
+    for edge in graph.edge_references() {
+        let source = graph[edge.source()].diagram.clone();
+        let target = &graph[edge.target()].diagram;
+        if source.rewrite_forward(&edge.weight().rewrite).ok().as_ref() != Some(target) {
+            return Err(ColimitError::Malformed(edge.id().index()));
+        }
+    }
+
+    let graph: ContractGraph<Ix> = graph.map(
+        |_, node| ScaffoldNode {
+            key: ContractNode {
+                bias: None,
+                coordinate: node.key.clone(),
+            },
+            diagram: node.diagram.clone(),
+        },
+        |_, edge| ScaffoldEdge {
+            key: (),
+            rewrite: edge.rewrite.clone(),
+        },
+    );
+
+    Ok(contraction::colimit(&graph)?)
+}
+
+/// Compute the pushout of a span `A <-h- S -k-> B`, together with its legs out of `A` and `B`.
+///
+/// The span is placed as the regular height between two singular heights of a zigzag.
+#[allow(clippy::many_single_char_names)]
+pub fn pushout(
+    a: &Diagram,
+    b: &Diagram,
+    s: &Diagram,
+    h: &Rewrite,
+    k: &Rewrite,
+) -> Result<(Diagram, Rewrite, Rewrite), ColimitError> {
+    let mut graph: Scaffold<Vec<Height>> = DiGraph::new();
+    let a_ix = graph.add_node(ScaffoldNode::new(vec![Height::Singular(0)], a.clone()));
+    let s_ix = graph.add_node(ScaffoldNode::new(vec![Height::Regular(1)], s.clone()));
+    let b_ix = graph.add_node(ScaffoldNode::new(vec![Height::Singular(1)], b.clone()));
+    graph.add_edge(s_ix, a_ix, h.clone().into());
+    graph.add_edge(s_ix, b_ix, k.clone().into());
+
+    let Cocone { colimit, legs } = colimit(&graph)?;
+    Ok((colimit, legs[a_ix].clone(), legs[b_ix].clone()))
+}
@@ -369,19 +369,21 @@ impl Diagram {
             .into_labeling()
             .into_iter()
             .flat_map(|ix| {
-                match stable[ix].key.clone() {
-                    OneMany::One(c) => vec![(c.clone(), OrdSet::unit(c))],
-                    OneMany::Many(cs) => {
-                        let shared = cs.clone();
-                        cs.into_iter().map(|c| (c, shared.clone())).collect()
-                    }
-                }
-                .into_iter()
+                let shared = identified(&stable[ix].key);
+                shared.clone().into_iter().map(move |c| (c, shared.clone()))
             })
             .collect()
     }
 }
 
+/// The coordinates of the nodes which were identified into a node of a collapsed scaffold.
+pub(crate) fn identified<V: Clone + Ord + Cartesian<Height>>(key: &Set<V>) -> OrdSet<Vec<Height>> {
+    match key {
+        OneMany::One(c) => OrdSet::unit(c.coordinate().to_vec()),
+        OneMany::Many(cs) => cs.iter().map(|c| c.coordinate().to_vec()).collect(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use petgraph::visit::{EdgeRef, IntoEdgeReferences};
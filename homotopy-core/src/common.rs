@@ -1,20 +1,17 @@
 use std::{
-    cell::RefCell,
     cmp::Ordering,
     fmt,
+    hash::Hash,
     ops::{Index, IndexMut, Mul},
 };
 
-use hashconsing::{HConsed, HConsign, HashConsign};
+use hashconsing::HConsed;
 use homotopy_common::{hash::FastHashMap, idx::Idx};
 use im::OrdSet;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-thread_local! {
-    static LABEL_FACTORY: RefCell<HConsign<LabelInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
-}
+use crate::session::{self, SessionId};
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Generator {
@@ -303,6 +300,49 @@ pub enum Direction {
 #[error("invalid dimension")]
 pub struct DimensionError;
 
+/// Where a contraction or expansion failed.
+///
+/// The depth counts how many times the problem was sliced before the failure was found, and the
+/// singular heights and cone indices refer to the diagrams at that depth.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    pub depth: usize,
+    pub heights: Vec<SingularHeight>,
+    pub cones: Vec<usize>,
+}
+
+impl Location {
+    pub(crate) fn new(heights: Vec<SingularHeight>, cones: Vec<usize>) -> Self {
+        Self {
+            depth: 0,
+            heights,
+            cones,
+        }
+    }
+
+    /// The same location, seen from one level further up.
+    #[must_use]
+    pub(crate) fn nested(self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at depth {}", self.depth)?;
+        if !self.heights.is_empty() {
+            write!(f, ", singular heights {:?}", self.heights)?;
+        }
+        if !self.cones.is_empty() {
+            write!(f, ", cones {:?}", self.cones)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub enum Mode {
     Deep,
@@ -340,9 +380,21 @@ impl fmt::Debug for Orientation {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq)]
 pub struct Label(HConsed<LabelInternal>);
 
+impl PartialEq for Label {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 .2 == other.0 .2 && self.0 == other.0
+    }
+}
+
+impl Hash for Label {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
 impl Serialize for Label {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -357,8 +409,7 @@ impl<'de> Deserialize<'de> for Label {
     where
         D: serde::Deserializer<'de>,
     {
-        Deserialize::deserialize(deserializer)
-            .map(|l| Label(LABEL_FACTORY.with(|factory| factory.borrow_mut().mk(l))))
+        Deserialize::deserialize(deserializer).map(|l| Label(session::label(l)))
     }
 }
 
@@ -373,11 +424,11 @@ impl fmt::Debug for Label {
 
 impl Label {
     pub fn new(boundary_path: BoundaryPath, coords: OrdSet<Vec<Height>>) -> Self {
-        Self(LABEL_FACTORY.with(|factory| {
-            factory
-                .borrow_mut()
-                .mk(LabelInternal(boundary_path, coords))
-        }))
+        Self(session::label(LabelInternal(
+            boundary_path,
+            coords,
+            SessionId::default(),
+        )))
     }
 
     pub fn boundary_path(&self) -> BoundaryPath {
@@ -387,13 +438,9 @@ impl Label {
     pub fn coords(&self) -> OrdSet<Vec<Height>> {
         self.0 .1.clone()
     }
-
-    pub(crate) fn collect_garbage() {
-        LABEL_FACTORY.with(|factory| factory.borrow_mut().collect_to_fit());
-    }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
-struct LabelInternal(BoundaryPath, OrdSet<Vec<Height>>);
+pub(crate) struct LabelInternal(BoundaryPath, OrdSet<Vec<Height>>, #[serde(skip)] SessionId);
 
 pub(crate) type LabelIdentifications = FastHashMap<Vec<Height>, OrdSet<Vec<Height>>>;
@@ -5,6 +5,7 @@ use std::{
 };
 
 use homotopy_common::{declare_idx, hash::FastHashMap, idx::IdxVec};
+use im::OrdSet;
 use itertools::Itertools;
 use petgraph::{
     adj::UnweightedList,
@@ -22,10 +23,14 @@ use thiserror::Error;
 
 use crate::{
     attach::attach,
-    collapse::{unify, Cartesian, Collapsible},
-    common::{Boundary, BoundaryPath, DimensionError, Height, Orientation, SingularHeight},
+    colimit::Cocone,
+    collapse::{identified, unify, Cartesian, Collapsible},
+    common::{
+        Boundary, BoundaryPath, DimensionError, Height, Label, Location, Orientation,
+        SingularHeight,
+    },
     diagram::{Diagram, Diagram0, DiagramN},
-    expansion::expand_propagate,
+    expansion::{expand_propagate, ExpansionError},
     rewrite::{Cone, Cospan, Rewrite, Rewrite0, RewriteN},
     scaffold::{
         Explodable, ExplosionOutput, ExternalRewrite, InternalRewrite, Scaffold, ScaffoldEdge,
@@ -56,8 +61,14 @@ impl Bias {
 
 #[derive(Debug, Error)]
 pub enum ContractionError {
-    #[error("contraction failed: label inconsistency")]
-    LabelInconsistency,
+    #[error(
+        "contraction failed: label inconsistency {location} between {labels:?} on the points {points:?}"
+    )]
+    LabelInconsistency {
+        location: Location,
+        labels: [Option<Label>; 2],
+        points: OrdSet<Vec<Height>>,
+    },
 
     #[error("contraction failed: max dimensional generator not connected to all others")]
     NonConnectedMaxDimensionGenerator,
@@ -68,13 +79,19 @@ pub enum ContractionError {
     #[error("contraction failed: orientation error")]
     Orientation,
 
-    #[error("contraction invalid")]
-    Invalid,
+    #[error("contraction invalid {0}")]
+    Invalid(Location),
+
+    #[error("contraction failed to propagate {location}: {source}")]
+    Propagation {
+        location: Location,
+        source: ExpansionError,
+    },
 
     #[error("contraction ambiguous")]
     Ambiguous,
 
-    #[error("contraction is ill-typed")]
+    #[error("contraction is ill-typed: {0}")]
     IllTyped(#[from] TypeError),
 
     #[error(transparent)]
@@ -84,9 +101,43 @@ pub enum ContractionError {
     OutOfBounds,
 }
 
-struct ContractExpand {
-    contract: RewriteN,
-    expand: RewriteN,
+impl ContractionError {
+    /// The same error, with its location seen from one level further up.
+    fn nested(self) -> Self {
+        match self {
+            Self::LabelInconsistency {
+                location,
+                labels,
+                points,
+            } => Self::LabelInconsistency {
+                location: location.nested(),
+                labels,
+                points,
+            },
+            Self::Invalid(location) => Self::Invalid(location.nested()),
+            Self::Propagation { location, source } => Self::Propagation {
+                location: location.nested(),
+                source,
+            },
+            error => error,
+        }
+    }
+
+    /// A failure to propagate the expansion of a slice at a singular height.
+    fn propagation(height: SingularHeight, error: ExpansionError) -> Self {
+        match error {
+            ExpansionError::FailedToPropagate(location) => Self::Invalid(location),
+            source => Self::Propagation {
+                location: Location::new(vec![height], vec![]),
+                source,
+            },
+        }
+    }
+}
+
+pub(crate) struct ContractExpand {
+    pub(crate) contract: RewriteN,
+    pub(crate) expand: RewriteN,
 }
 
 impl DiagramN {
@@ -121,6 +172,55 @@ impl DiagramN {
     }
 }
 
+impl DiagramN {
+    /// Every well-typed contraction at a location, each tagged with the bias which produces it.
+    ///
+    /// Biases are tried in the order `None`, `Higher`, `Same`, `Lower` and only the first bias
+    /// producing each distinct diagram is kept. If no bias gives a valid contraction, the error of
+    /// the unbiased contraction is returned.
+    pub fn contract_all(
+        &self,
+        boundary_path: BoundaryPath,
+        interior_path: &[Height],
+        height: SingularHeight,
+        direction: Direction,
+        signature: &impl Signature,
+    ) -> Result<Vec<(Option<Bias>, Self)>, ContractionError> {
+        let mut contractions: Vec<(Option<Bias>, Self)> = vec![];
+        let mut error = None;
+
+        for bias in [
+            None,
+            Some(Bias::Higher),
+            Some(Bias::Same),
+            Some(Bias::Lower),
+        ] {
+            match self.contract(
+                boundary_path,
+                &mut interior_path.to_vec(),
+                height,
+                direction,
+                bias,
+                signature,
+            ) {
+                Ok(diagram) => {
+                    if contractions.iter().all(|(_, d)| *d != diagram) {
+                        contractions.push((bias, diagram));
+                    }
+                }
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+
+        match error {
+            Some(e) if contractions.is_empty() => Err(e),
+            _ => Ok(contractions),
+        }
+    }
+}
+
 fn contract_base(
     diagram: &DiagramN,
     height: SingularHeight,
@@ -291,7 +391,7 @@ fn contract_base(
     Ok(ContractExpand { contract, expand })
 }
 
-fn contract_in_path(
+pub(crate) fn contract_in_path(
     diagram: &DiagramN,
     path: &mut [Height],
     height: SingularHeight,
@@ -311,7 +411,8 @@ fn contract_in_path(
             let ContractExpand {
                 contract: contract_base,
                 expand: expand_base,
-            } = contract_in_path(&slice, rest, height, direction, bias, false)?;
+            } = contract_in_path(&slice, rest, height, direction, bias, false)
+                .map_err(ContractionError::nested)?;
             match *step {
                 Regular(i) => {
                     *step = Singular(i);
@@ -332,7 +433,7 @@ fn contract_in_path(
                         expand_base.into(),
                         false,
                     )
-                    .map_err(|_err| ContractionError::Invalid)?;
+                    .map_err(|err| ContractionError::propagation(i, err))?;
                     Ok(ContractExpand { contract, expand })
                 }
                 Singular(i) => {
@@ -401,7 +502,7 @@ fn contract_in_path(
                         expand_base.into(),
                         true,
                     )
-                    .map_err(|_err| ContractionError::Invalid)?;
+                    .map_err(|err| ContractionError::propagation(i, err))?;
                     Ok(ContractExpand { contract, expand })
                 }
             }
@@ -409,19 +510,10 @@ fn contract_in_path(
     }
 }
 
-#[derive(Debug)]
-struct Cocone<Ix = DefaultIx>
-where
-    Ix: IndexType,
-{
-    colimit: Diagram,
-    legs: IdxVec<NodeIndex<Ix>, Rewrite>,
-}
-
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct ContractNode {
-    bias: Option<Bias>,
-    coordinate: Vec<Height>,
+pub(crate) struct ContractNode {
+    pub(crate) bias: Option<Bias>,
+    pub(crate) coordinate: Vec<Height>,
 }
 
 impl Cartesian<Height> for ContractNode {
@@ -430,13 +522,28 @@ impl Cartesian<Height> for ContractNode {
     }
 }
 
-type ContractGraph<Ix> = Scaffold<ContractNode, (), Ix>;
+pub(crate) type ContractGraph<Ix> = Scaffold<ContractNode, (), Ix>;
+
+/// The singular heights at which the given nodes sit in the diagram they were sliced from.
+fn singular_heights<'a>(nodes: impl IntoIterator<Item = &'a ContractNode>) -> Vec<SingularHeight> {
+    nodes
+        .into_iter()
+        .filter_map(|node| match node.coordinate.last() {
+            Some(&Height::Singular(height)) => Some(height),
+            _ => None,
+        })
+        .sorted()
+        .dedup()
+        .collect()
+}
 
-fn colimit<Ix: IndexType>(graph: &ContractGraph<Ix>) -> Result<Cocone<Ix>, ContractionError> {
+pub(crate) fn colimit<Ix: IndexType>(
+    graph: &ContractGraph<Ix>,
+) -> Result<Cocone<Ix>, ContractionError> {
     let dimension = graph
         .node_weights()
         .next()
-        .ok_or(ContractionError::Invalid)?
+        .ok_or_else(|| ContractionError::Invalid(Location::default()))?
         .diagram
         .dimension();
 
@@ -569,8 +676,13 @@ fn colimit_base<Ix: IndexType>(graph: &ContractGraph<Ix>) -> Result<Cocone<Ix>,
                     let label = labels
                         .next()
                         .ok_or(ContractionError::NonConnectedMaxDimensionGenerator)?;
-                    if labels.any(|l| l != label) {
-                        return Err(ContractionError::LabelInconsistency);
+                    if let Some(other) = labels.find(|l| *l != label) {
+                        let heights = singular_heights([n, max_dim_index].map(|i| &graph[i].key));
+                        return Err(ContractionError::LabelInconsistency {
+                            location: Location::new(heights, vec![]),
+                            labels: [label.cloned(), other.cloned()],
+                            points: identified(&stable[p].key),
+                        });
                     }
                     Rewrite0::new(d, colimit, label.cloned())
                 }
@@ -638,7 +750,16 @@ fn colimit_recursive<Ix: IndexType>(
                 _ => Some(None),
             },
         )
-        .map_err(|_err| ContractionError::Invalid)?;
+        .map_err(|_err| {
+            // only 0-dimensional nodes can not be exploded
+            let heights = singular_heights(
+                graph
+                    .node_weights()
+                    .filter(|node| node.diagram.dimension() == 0)
+                    .map(|node| &node.key),
+            );
+            ContractionError::Invalid(Location::new(heights, vec![]))
+        })?;
 
     // Find colimit in Δ (determines the order of subproblem solutions as singular heights in the
     // constructed colimit)
@@ -956,7 +1077,8 @@ fn colimit_recursive<Ix: IndexType>(
                 },
                 |_, ScaffoldEdge { rewrite, .. }| Some(rewrite.clone().into()),
             );
-            let cocone: Cocone<RestrictionIx> = colimit(&restriction)?;
+            let cocone: Cocone<RestrictionIx> =
+                colimit(&restriction).map_err(ContractionError::nested)?;
             Ok((source_ix, cocone, target_ix, restriction_to_exploded))
         })
         .fold_ok(vec![], |mut acc, x| {
@@ -965,7 +1087,9 @@ fn colimit_recursive<Ix: IndexType>(
         })?;
 
     // assemble solutions
-    let (s, first, _, _) = cocones.first().ok_or(ContractionError::Invalid)?;
+    let (s, first, _, restriction_to_exploded) = cocones
+        .first()
+        .ok_or_else(|| ContractionError::Invalid(Location::default()))?;
     let colimit: DiagramN = if let Ok(terminal) = graph.externals(Outgoing).exactly_one() {
         DiagramN::try_from(graph[terminal].diagram.clone()).unwrap()
     } else {
@@ -974,7 +1098,11 @@ fn colimit_recursive<Ix: IndexType>(
                 .colimit
                 .clone()
                 .rewrite_backward(&first.legs[*s])
-                .map_err(|_err| ContractionError::Invalid)?,
+                .map_err(|_err| {
+                    let parent = exploded[restriction_to_exploded[*s]].key.parent;
+                    let heights = singular_heights([&graph[parent].key]);
+                    ContractionError::Invalid(Location::new(heights, vec![]))
+                })?,
             cocones
                 .iter()
                 .map(|(source, cocone, target, _)| Cospan {
@@ -0,0 +1,288 @@
+use std::{collections::BTreeSet, ops::RangeInclusive, rc::Rc};
+
+use thiserror::Error;
+
+use crate::{
+    common::Height,
+    typecheck::{clear_restrict_cache, restrict_diagram, Embedding},
+    Diagram, DiagramN, Rewrite,
+};
+
+type Point = Vec<Height>;
+type Region = BTreeSet<Point>;
+
+#[derive(Debug, Error)]
+pub enum CropError {
+    #[error("the box has more dimensions than the diagram")]
+    Dimension,
+
+    #[error("the box does not contain any point of the diagram")]
+    Empty,
+}
+
+impl DiagramN {
+    /// Cut out the smallest subdiagram containing every point inside a box.
+    ///
+    /// The box is given by a range of heights in each dimension, outermost first, and a point is
+    /// inside it when each of its coordinates lies in the corresponding range. Dimensions past
+    /// the end of `bounds` are not restricted. The subdiagram is closed under the neighbourhoods
+    /// of its points, so it extends past the box wherever a cell crosses its boundary.
+    pub fn crop(&self, bounds: &[RangeInclusive<Height>]) -> Result<Self, CropError> {
+        if bounds.len() > self.dimension() {
+            return Err(CropError::Dimension);
+        }
+
+        let diagram: Diagram = self.clone().into();
+        let region: Region = points(&diagram)
+            .into_iter()
+            .filter(|point| point.iter().zip(bounds).all(|(h, r)| r.contains(h)))
+            .collect();
+
+        if region.is_empty() {
+            return Err(CropError::Empty);
+        }
+
+        let (embedding, _) = close(&diagram, &region);
+        let cropped = restrict_diagram(&diagram, &embedding);
+        clear_restrict_cache();
+
+        Ok(cropped.try_into().unwrap())
+    }
+}
+
+/// All points of a diagram.
+fn points(diagram: &Diagram) -> Vec<Point> {
+    match diagram {
+        Diagram::Diagram0(_) => vec![vec![]],
+        Diagram::DiagramN(d) => Height::for_size(d.size())
+            .zip(d.slices())
+            .flat_map(|(height, slice)| {
+                points(&slice).into_iter().map(move |mut point| {
+                    point.insert(0, height);
+                    point
+                })
+            })
+            .collect(),
+    }
+}
+
+fn slice(region: &Region, height: Height) -> Region {
+    region
+        .iter()
+        .filter(|point| point[0] == height)
+        .map(|point| point[1..].to_vec())
+        .collect()
+}
+
+fn lift(region: Region, height: Height) -> impl Iterator<Item = Point> {
+    region.into_iter().map(move |mut point| {
+        point.insert(0, height);
+        point
+    })
+}
+
+/// Find the smallest subdiagram containing a non-empty region, returning its embedding and the
+/// points it covers.
+///
+/// A subdiagram is an interval of heights in the outermost dimension together with a subdiagram
+/// of each slice in the interval, such that the subdiagram of each singular slice contains the
+/// image of the adjacent regular ones, and the subdiagram of each regular slice is precisely the
+/// preimage of the adjacent singular ones.
+fn close(diagram: &Diagram, region: &Region) -> (Embedding, Region) {
+    use Height::{Regular, Singular};
+
+    let Diagram::DiagramN(diagram) = diagram else {
+        return (Embedding::Zero, region.clone());
+    };
+
+    let lo = region
+        .iter()
+        .map(|point| match point[0] {
+            Regular(i) | Singular(i) => i,
+        })
+        .min()
+        .unwrap();
+    let hi = region
+        .iter()
+        .map(|point| match point[0] {
+            Regular(i) => i,
+            Singular(i) => i + 1,
+        })
+        .max()
+        .unwrap();
+
+    if lo == hi {
+        let (embedding, covered) = close(
+            &diagram.slice(Regular(lo)).unwrap(),
+            &slice(region, Regular(lo)),
+        );
+        return (
+            Embedding::Regular(lo, Rc::new(embedding)),
+            lift(covered, Regular(lo)).collect(),
+        );
+    }
+
+    let cospans = &diagram.cospans()[lo..hi];
+    let regular_slices: Vec<Diagram> = (lo..=hi)
+        .map(|i| diagram.slice(Regular(i)).unwrap())
+        .collect();
+    let singular_slices: Vec<Diagram> = (lo..hi)
+        .map(|i| diagram.slice(Singular(i)).unwrap())
+        .collect();
+
+    let mut regular: Vec<Region> = (lo..=hi).map(|i| slice(region, Regular(i))).collect();
+    let mut singular: Vec<Region> = (lo..hi).map(|i| slice(region, Singular(i))).collect();
+    let mut embeddings: Vec<Option<Rc<Embedding>>> = vec![None; cospans.len()];
+
+    loop {
+        let mut changed = false;
+
+        for (i, cospan) in cospans.iter().enumerate() {
+            let mut points = singular[i].clone();
+            points.extend(image(&cospan.forward, &regular[i]));
+            points.extend(image(&cospan.backward, &regular[i + 1]));
+
+            // Levels between the points of the region are filled in from their neighbours.
+            if points.is_empty() {
+                changed = true;
+                continue;
+            }
+
+            let (embedding, covered) = close(&singular_slices[i], &points);
+            embeddings[i] = Some(Rc::new(embedding));
+            singular[i] = covered;
+        }
+
+        for (i, source) in regular_slices.iter().enumerate() {
+            let mut points = Region::new();
+            if i > 0 {
+                points.extend(preimage(&cospans[i - 1].backward, source, &singular[i - 1]));
+            }
+            if i < cospans.len() {
+                points.extend(preimage(&cospans[i].forward, source, &singular[i]));
+            }
+
+            for point in points {
+                changed |= regular[i].insert(point);
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let covered = regular
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, r)| lift(r, Regular(lo + i)))
+        .chain(
+            singular
+                .into_iter()
+                .enumerate()
+                .flat_map(|(i, r)| lift(r, Singular(lo + i))),
+        )
+        .collect();
+    let embeddings = embeddings.into_iter().map(Option::unwrap).collect();
+
+    (Embedding::Singular(lo, embeddings), covered)
+}
+
+/// The points of the target of a rewrite which must be in a subdiagram containing the given
+/// points of the source.
+fn image(rewrite: &Rewrite, region: &Region) -> Region {
+    region
+        .iter()
+        .flat_map(|point| image_point(rewrite, point))
+        .collect()
+}
+
+fn image_point(rewrite: &Rewrite, point: &[Height]) -> Vec<Point> {
+    use Height::{Regular, Singular};
+
+    let Rewrite::RewriteN(rewrite) = rewrite else {
+        return vec![point.to_vec()];
+    };
+
+    let lift = |height: Height, points: Vec<Point>| {
+        points.into_iter().map(move |mut p| {
+            p.insert(0, height);
+            p
+        })
+    };
+
+    match point[0] {
+        Singular(i) => lift(
+            Singular(rewrite.singular_image(i)),
+            image_point(&rewrite.slice(i), &point[1..]),
+        )
+        .collect(),
+        Regular(i) => {
+            let targets = rewrite.regular_preimage(i);
+            if !targets.is_empty() {
+                return targets
+                    .map(|j| {
+                        let mut p = point.to_vec();
+                        p[0] = Regular(j);
+                        p
+                    })
+                    .collect();
+            }
+
+            // The regular height is inside a cone, so it is sent to the target of the cone.
+            let (target, cone) = rewrite
+                .targets()
+                .into_iter()
+                .zip(rewrite.cones())
+                .find(|(_, cone)| cone.index < i && i < cone.index + cone.len())
+                .unwrap();
+            lift(
+                Singular(target),
+                image_point(&cone.regular_slices()[i - cone.index], &point[1..]),
+            )
+            .collect()
+        }
+    }
+}
+
+/// The points of the source of a rewrite lying over the given subdiagram of the target.
+fn preimage(rewrite: &Rewrite, source: &Diagram, region: &Region) -> Region {
+    points(source)
+        .into_iter()
+        .filter(|point| over(rewrite, point, region))
+        .collect()
+}
+
+fn over(rewrite: &Rewrite, point: &[Height], region: &Region) -> bool {
+    use Height::{Regular, Singular};
+
+    let Rewrite::RewriteN(rewrite) = rewrite else {
+        return region.contains(point);
+    };
+
+    match point[0] {
+        Singular(i) => over(
+            &rewrite.slice(i),
+            &point[1..],
+            &slice(region, Singular(rewrite.singular_image(i))),
+        ),
+        Regular(i) => {
+            rewrite.regular_preimage(i).any(|j| {
+                let mut p = point.to_vec();
+                p[0] = Regular(j);
+                region.contains(&p)
+            }) || rewrite
+                .targets()
+                .into_iter()
+                .zip(rewrite.cones())
+                .filter(|(_, cone)| cone.index <= i && i <= cone.index + cone.len())
+                .any(|(target, cone)| {
+                    over(
+                        &cone.regular_slices()[i - cone.index],
+                        &point[1..],
+                        &slice(region, Singular(target)),
+                    )
+                })
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use thiserror::Error;
+
+use crate::{
+    common::{Generator, Label},
+    rewrite::Cone,
+    signature::{GeneratorInfo, Signature},
+    Cospan, Diagram, Diagram0, DiagramN, Rewrite, Rewrite0, RewriteN,
+};
+
+#[derive(Debug, Error)]
+pub enum DesuspensionError {
+    #[error("a diagram of dimension 0 cannot be desuspended")]
+    Dimension,
+
+    #[error("{0:?} is not suspended over the base generators")]
+    NotSuspended(Generator),
+}
+
+/// The inverse of [`Diagram::suspend`], which lowers diagrams suspended over a pair of base
+/// generators by one dimension.
+///
+/// Every generator other than the base generators goes down by one dimension and keeps its id.
+/// Desuspension fails if a diagram is not of the form of a suspension, for example because it
+/// contains a generator of dimension 0 other than the base generators or because the base
+/// generators appear anywhere other than as the source and target points of the suspension.
+#[derive(Copy, Clone, Debug)]
+pub struct Desuspension {
+    source: Generator,
+    target: Generator,
+}
+
+impl Desuspension {
+    pub fn new(source: Generator, target: Generator) -> Self {
+        Self { source, target }
+    }
+
+    fn is_base(&self, generator: Generator) -> bool {
+        generator == self.source || generator == self.target
+    }
+
+    fn generator(&self, generator: Generator) -> Result<Generator, DesuspensionError> {
+        if self.is_base(generator) || generator.dimension == 0 {
+            return Err(DesuspensionError::NotSuspended(generator));
+        }
+        Ok(Generator::new(generator.id, generator.dimension - 1))
+    }
+
+    fn diagram0(&self, diagram: Diagram0) -> Result<Diagram0, DesuspensionError> {
+        Ok(Diagram0::new(
+            self.generator(diagram.generator)?,
+            diagram.orientation,
+        ))
+    }
+
+    /// Check that a rewrite of dimension 0 goes from a base generator to a suspended point.
+    fn base_rewrite(rewrite: &Rewrite, base: Generator) -> Result<Diagram0, DesuspensionError> {
+        let Rewrite::Rewrite0(rewrite) = rewrite else {
+            return Err(DesuspensionError::Dimension);
+        };
+        match (rewrite.source(), rewrite.target()) {
+            (Some(source), Some(target)) if source.generator == base => Ok(target),
+            (source, _) => Err(DesuspensionError::NotSuspended(
+                source.map_or(base, |source| source.generator),
+            )),
+        }
+    }
+
+    /// Check that a cospan of dimension 0 is the suspension of a point, returning the point.
+    fn point(&self, cospan: &Cospan) -> Result<Diagram0, DesuspensionError> {
+        let forward = Self::base_rewrite(&cospan.forward, self.source)?;
+        let backward = Self::base_rewrite(&cospan.backward, self.target)?;
+        if forward != backward {
+            return Err(DesuspensionError::NotSuspended(forward.generator));
+        }
+        self.diagram0(forward)
+    }
+
+    /// Apply the desuspension to a diagram.
+    pub fn apply(&self, diagram: &Diagram) -> Result<Diagram, DesuspensionError> {
+        match diagram {
+            Diagram::Diagram0(_) => Err(DesuspensionError::Dimension),
+            Diagram::DiagramN(d) => self.apply_n(d),
+        }
+    }
+
+    fn apply_n(&self, diagram: &DiagramN) -> Result<Diagram, DesuspensionError> {
+        if diagram.dimension() == 1 {
+            let Diagram::Diagram0(source) = diagram.source() else {
+                unreachable!()
+            };
+            if source.generator != self.source {
+                return Err(DesuspensionError::NotSuspended(source.generator));
+            }
+            let [cospan] = diagram.cospans() else {
+                return Err(DesuspensionError::NotSuspended(source.generator));
+            };
+            return Ok(self.point(cospan)?.into());
+        }
+
+        let cospans = diagram
+            .cospans()
+            .iter()
+            .map(|cospan| self.cospan(cospan))
+            .collect::<Result<_, _>>()?;
+        Ok(DiagramN::new(self.apply(&diagram.source())?, cospans).into())
+    }
+
+    fn cospan(&self, cospan: &Cospan) -> Result<Cospan, DesuspensionError> {
+        Ok(Cospan {
+            forward: self.apply_rewrite(&cospan.forward)?,
+            backward: self.apply_rewrite(&cospan.backward)?,
+        })
+    }
+
+    /// Apply the desuspension to a rewrite.
+    pub fn apply_rewrite(&self, rewrite: &Rewrite) -> Result<Rewrite, DesuspensionError> {
+        let Rewrite::RewriteN(rewrite) = rewrite else {
+            return Err(DesuspensionError::Dimension);
+        };
+
+        if rewrite.dimension() > 1 {
+            let cones = rewrite
+                .cones()
+                .iter()
+                .map(|cone| self.cone(cone))
+                .collect::<Result<_, _>>()?;
+            return Ok(RewriteN::new(rewrite.dimension() - 1, cones).into());
+        }
+
+        // A rewrite between suspended points is the suspension of a rewrite between the points.
+        match rewrite.cones() {
+            [] => Ok(Rewrite0::identity().into()),
+            [cone] => {
+                let ([source], [slice]) = (cone.source(), cone.singular_slices()) else {
+                    return Err(DesuspensionError::NotSuspended(self.source));
+                };
+                self.point(source)?;
+                self.point(cone.target())?;
+
+                let Rewrite::Rewrite0(slice) = slice else {
+                    return Err(DesuspensionError::Dimension);
+                };
+                let (Some(source), Some(target)) = (slice.source(), slice.target()) else {
+                    return Err(DesuspensionError::NotSuspended(self.source));
+                };
+                Ok(Rewrite0::new(
+                    self.diagram0(source)?,
+                    self.diagram0(target)?,
+                    slice.label().map(desuspend_label),
+                )
+                .into())
+            }
+            _ => Err(DesuspensionError::NotSuspended(self.source)),
+        }
+    }
+
+    fn cone(&self, cone: &Cone) -> Result<Cone, DesuspensionError> {
+        Ok(Cone::new(
+            cone.index,
+            cone.source()
+                .iter()
+                .map(|cospan| self.cospan(cospan))
+                .collect::<Result<_, _>>()?,
+            self.cospan(cone.target())?,
+            cone.regular_slices()
+                .iter()
+                .map(|slice| self.apply_rewrite(slice))
+                .collect::<Result<_, _>>()?,
+            cone.singular_slices()
+                .iter()
+                .map(|slice| self.apply_rewrite(slice))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    /// Apply the desuspension to the diagrams of the generators of a signature other than the
+    /// base generators, returning each desuspended generator with its diagram.
+    pub fn apply_signature(
+        &self,
+        signature: &impl Signature,
+    ) -> Result<Vec<(Generator, Diagram)>, DesuspensionError> {
+        signature
+            .generators()
+            .filter(|generator| !self.is_base(*generator))
+            .map(|generator| {
+                let info = signature.generator_info(generator).unwrap();
+                Ok((self.generator(generator)?, self.apply(info.diagram())?))
+            })
+            .collect()
+    }
+}
+
+/// Labels of suspended points carry an extra coordinate for the height in the suspension.
+fn desuspend_label(label: &Label) -> Label {
+    Label::new(
+        label.boundary_path(),
+        label
+            .coords()
+            .iter()
+            .map(|coord| coord[..coord.len().saturating_sub(1)].to_vec())
+            .collect(),
+    )
+}
@@ -1,13 +1,12 @@
 use std::{
-    cell::RefCell,
     convert::{From, Into, TryFrom},
     fmt,
     hash::Hash,
 };
 
-use hashconsing::{HConsed, HConsign, HashConsign};
+use hashconsing::HConsed;
 use homotopy_common::hash::{FastHashMap, FastHashSet};
-use once_cell::unsync::OnceCell;
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -18,15 +17,11 @@ use crate::{
         RegularHeight, SliceIndex,
     },
     rewrite::{Cospan, Rewrite, Rewrite0, RewriteN},
+    session::{self, SessionId},
     signature::{GeneratorInfo, Signature},
     Orientation,
 };
 
-thread_local! {
-    static DIAGRAM_FACTORY: RefCell<HConsign<DiagramInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
-}
-
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Diagram {
     Diagram0(Diagram0),
@@ -223,6 +218,22 @@ impl Diagram {
             Self::DiagramN(d) => d.suspend(s, t),
         }
     }
+
+    /// Reverse the direction in which the `k`-cells of the diagram go, as in the diagram of the
+    /// same shape over the dual signature in which each cell of dimension at least `k` is
+    /// reversed in dimension `k`. Reversing in the top dimension is the same as taking the
+    /// inverse, but without inverting the cells.
+    ///
+    /// Labels are kept as they are, so that a reversed diagram is well-typed in the signature
+    /// whose generators have reversed diagrams, as constructed by [`crate::signature::dual`].
+    #[must_use]
+    pub fn reverse_in(&self, k: usize) -> Self {
+        match self {
+            Self::Diagram0(d) => Self::Diagram0(*d),
+            Self::DiagramN(d) => Self::DiagramN(d.reverse_in(k)),
+        }
+    }
+
     #[must_use]
     pub fn replace(&self, from: Generator, to: Generator, oriented: bool) -> Diagram {
         match self {
@@ -322,9 +333,21 @@ impl From<Generator> for Diagram0 {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq)]
 pub struct DiagramN(HConsed<DiagramInternal>);
 
+impl PartialEq for DiagramN {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.session == other.0.session && self.0 == other.0
+    }
+}
+
+impl Hash for DiagramN {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
 impl Serialize for DiagramN {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -339,8 +362,7 @@ impl<'de> Deserialize<'de> for DiagramN {
     where
         D: serde::Deserializer<'de>,
     {
-        Deserialize::deserialize(deserializer)
-            .map(|d| DiagramN(DIAGRAM_FACTORY.with(|factory| factory.borrow_mut().mk(d))))
+        Deserialize::deserialize(deserializer).map(|d| DiagramN(session::diagram(d)))
     }
 }
 
@@ -402,12 +424,11 @@ impl DiagramN {
     /// Unsafe version of `new` which does not check if the diagram is well-formed.
     #[inline]
     pub(crate) fn new_unsafe(source: Diagram, cospans: Vec<Cospan>) -> Self {
-        Self(DIAGRAM_FACTORY.with(|factory| {
-            factory.borrow_mut().mk(DiagramInternal {
-                source,
-                cospans,
-                max_generator: OnceCell::new(),
-            })
+        Self(session::diagram(DiagramInternal {
+            source,
+            cospans,
+            max_generator: OnceCell::new(),
+            session: SessionId::default(),
         }))
     }
 
@@ -434,8 +455,40 @@ impl DiagramN {
         )
     }
 
-    pub(crate) fn collect_garbage() {
-        DIAGRAM_FACTORY.with(|factory| factory.borrow_mut().collect_to_fit());
+    /// See [`Diagram::reverse_in`].
+    #[must_use]
+    pub fn reverse_in(&self, k: usize) -> Self {
+        if k == 0 || k > self.dimension() {
+            return self.clone();
+        }
+
+        if k == self.dimension() {
+            return Self::new(
+                self.target(),
+                self.cospans()
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .map(Cospan::flip)
+                    .collect(),
+            );
+        }
+
+        let slices: Vec<Diagram> = self.slices().collect();
+        let cospans = self
+            .cospans()
+            .iter()
+            .enumerate()
+            .map(|(i, cospan)| Cospan {
+                forward: cospan
+                    .forward
+                    .reverse_in(k, &slices[2 * i], &slices[2 * i + 1]),
+                backward: cospan
+                    .backward
+                    .reverse_in(k, &slices[2 * i + 2], &slices[2 * i + 1]),
+            })
+            .collect();
+        Self::new(self.source().reverse_in(k), cospans)
     }
 
     /// The dimension of the diagram, which is at least one.
@@ -505,7 +558,7 @@ impl DiagramN {
         for cone in rewrite.cones() {
             let start = (cone.index as isize + offset) as usize;
             let stop = (cone.index as isize + cone.len() as isize + offset) as usize;
-            if &cospans[start..stop] != cone.source() {
+            if cospans.get(start..stop) != Some(cone.source()) {
                 return Err(RewritingError::Incompatible);
             }
             cospans.splice(start..stop, std::iter::once(cone.target().clone()));
@@ -582,8 +635,36 @@ impl DiagramN {
             Ordering::Less => Embeddings(Box::new(std::iter::empty())),
             Ordering::Equal => {
                 let diagram = Self::try_from(diagram.clone()).unwrap();
-                let embeddings = self.embeddings_slice(diagram.source());
+
+                // Padding a cospan does not change the generators it mentions, so the diagram can
+                // only start at heights where the maximal generators of the cospans line up.
+                let candidates: Vec<bool> = (0..=self.size())
+                    .map(|start| {
+                        self.cospans()
+                            .get(start..diagram.size() + start)
+                            .is_some_and(|cospans| {
+                                cospans
+                                    .iter()
+                                    .map(Cospan::max_generator)
+                                    .eq(diagram.cospans().iter().map(Cospan::max_generator))
+                            })
+                    })
+                    .collect();
+
                 let haystack = self.clone();
+                let embeddings = self
+                    .regular_slices()
+                    .enumerate()
+                    .filter(move |(start, _)| candidates[*start])
+                    .flat_map({
+                        let diagram = diagram.clone();
+                        move |(start, slice)| {
+                            slice.embeddings(&diagram.source()).map(move |mut emb| {
+                                emb.insert(0, start);
+                                emb
+                            })
+                        }
+                    });
                 Embeddings(Box::new(embeddings.filter(move |embedding| {
                     let (start, rest) = embedding.split_first().unwrap();
                     haystack.cospans().get(*start..diagram.size() + *start)
@@ -656,6 +737,111 @@ impl DiagramN {
         })
     }
 
+    /// Attach a [diagram] to this diagram at the specified [boundary] at every position where
+    /// it fits, producing a single diagram in which all the attachments happen one after another.
+    ///
+    /// Embeddings are chosen greedily in the order they are found, skipping any that overlaps an
+    /// embedding already chosen. Fails if the diagram does not fit anywhere.
+    pub fn attach_everywhere(
+        &self,
+        diagram: &Self,
+        boundary: Boundary,
+    ) -> Result<Self, AttachmentError> {
+        let depth = self
+            .dimension()
+            .checked_sub(diagram.dimension())
+            .ok_or(DimensionError)?;
+
+        let needle = diagram.slice(boundary.flip()).unwrap();
+        let haystack = self
+            .boundary(BoundaryPath(boundary, depth))
+            .ok_or(AttachmentError::IncompatibleAttachment)?;
+        let extents = extents(&needle);
+
+        let mut chosen: Vec<Vec<RegularHeight>> = Vec::new();
+        for embedding in haystack.embeddings(&needle) {
+            if chosen
+                .iter()
+                .all(|other| !overlaps(&extents, &embedding, other))
+            {
+                chosen.push(embedding);
+            }
+        }
+
+        if chosen.is_empty() {
+            return Err(AttachmentError::IncompatibleAttachment);
+        }
+
+        // Attaching only changes the boundary at or after the embedding, so working from the
+        // last embedding backwards leaves the remaining ones intact.
+        chosen.sort();
+
+        chosen
+            .iter()
+            .rev()
+            .try_fold(self.clone(), |result, embedding| {
+                result.attach(diagram, boundary, embedding)
+            })
+    }
+
+    /// Paste `other` onto this diagram along the boundary at the given depth.
+    ///
+    /// The boundary of this diagram at `BoundaryPath(Boundary::Target, depth)` must agree with
+    /// the boundary of `other` at `BoundaryPath(Boundary::Source, depth)`. The levels of this
+    /// diagram come first, followed by the levels of `other` padded past the target of this
+    /// diagram.
+    pub fn compose(&self, other: &Self, depth: usize) -> Result<Self, PastingError> {
+        if self.dimension() != other.dimension() {
+            return Err(DimensionError.into());
+        }
+
+        if depth >= self.dimension() {
+            return Err(PastingError::Depth(self.dimension(), depth));
+        }
+
+        let path = BoundaryPath(Boundary::Target, depth);
+        let target = self.boundary(path);
+        let source = other.boundary(BoundaryPath(Boundary::Source, depth));
+        if target != source {
+            let height = match (target, source) {
+                (Some(Diagram::DiagramN(target)), Some(Diagram::DiagramN(source))) => {
+                    // When one boundary extends the other, they differ just after the shorter.
+                    let index = std::iter::zip(target.slices(), source.slices())
+                        .position(|(t, s)| t != s)
+                        .unwrap_or(2 * target.size().min(source.size()) + 1);
+                    Some(Height::from(index))
+                }
+                _ => None,
+            };
+            return Err(PastingError::BoundaryMismatch(path, height));
+        }
+
+        Ok(self.compose_worker(other, depth))
+    }
+
+    fn compose_worker(&self, other: &Self, depth: usize) -> Self {
+        let source = if depth == 0 {
+            self.source()
+        } else {
+            let source: Self = self.source().try_into().unwrap();
+            source
+                .compose_worker(&other.source().try_into().unwrap(), depth - 1)
+                .into()
+        };
+
+        let mut embedding = Vec::with_capacity(depth);
+        let mut target = self.target();
+        for _ in 0..depth {
+            let diagram: Self = target.try_into().unwrap();
+            embedding.push(diagram.size());
+            target = diagram.target();
+        }
+
+        let mut cospans = self.cospans().to_vec();
+        cospans.extend(other.cospans().iter().map(|c| c.pad(&embedding)));
+        Self::new(source, cospans)
+    }
+
     #[must_use]
     pub fn inverse(&self) -> Self {
         Self::new(
@@ -689,11 +875,13 @@ impl DiagramN {
 }
 
 #[derive(Clone, Eq, Serialize, Deserialize)]
-struct DiagramInternal {
+pub(crate) struct DiagramInternal {
     source: Diagram,
     cospans: Vec<Cospan>,
     #[serde(skip)]
     max_generator: OnceCell<Diagram0>,
+    #[serde(skip)]
+    session: SessionId,
 }
 
 impl PartialEq for DiagramInternal {
@@ -877,6 +1065,34 @@ impl ExactSizeIterator for Slices {
 
 impl std::iter::FusedIterator for Slices {}
 
+/// The largest size of the diagram and its slices in each dimension, from the top down.
+fn extents(diagram: &Diagram) -> Vec<usize> {
+    let Diagram::DiagramN(diagram) = diagram else {
+        return vec![];
+    };
+    let mut result = vec![diagram.size()];
+    for slice in diagram.slices() {
+        let slice_extents = extents(&slice);
+        result.resize(slice_extents.len() + 1, 0);
+        for (extent, slice_extent) in result[1..].iter_mut().zip(slice_extents) {
+            *extent = std::cmp::max(*extent, slice_extent);
+        }
+    }
+    result
+}
+
+/// Whether the boxes spanned by two embeddings of a diagram with the given extents share an
+/// interior point. Boxes that only touch along a regular slice do not overlap.
+fn overlaps(extents: &[usize], a: &[RegularHeight], b: &[RegularHeight]) -> bool {
+    extents
+        .iter()
+        .zip(a.iter().zip(b))
+        .all(|(extent, (a, b))| match extent {
+            0 => a == b,
+            _ => a.abs_diff(*b) < *extent,
+        })
+}
+
 pub struct Embeddings(Box<dyn Iterator<Item = Vec<RegularHeight>>>);
 
 impl Iterator for Embeddings {
@@ -907,6 +1123,21 @@ pub enum AttachmentError {
     IncompatibleAttachment,
 }
 
+#[derive(Debug, Error)]
+pub enum PastingError {
+    #[error("cannot paste diagrams of different dimensions")]
+    Dimension(#[from] DimensionError),
+
+    #[error("cannot paste diagrams of dimension {0} along a boundary at depth {1}")]
+    Depth(usize, usize),
+
+    #[error(
+        "boundary {0:?} of the first diagram does not match the second diagram{}",
+        .1.map_or_else(String::new, |height| format!(" at height {height:?}"))
+    )]
+    BoundaryMismatch(BoundaryPath, Option<Height>),
+}
+
 #[derive(Clone, Debug, Error)]
 pub enum RewritingError {
     #[error("can't rewrite diagram of dimension {0} along a rewrite of dimension {1}")]
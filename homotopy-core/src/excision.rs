@@ -0,0 +1,193 @@
+use thiserror::Error;
+
+use crate::{
+    common::{Direction, Height, Mode, SingularHeight},
+    contraction::{contract_in_path, Bias, ContractExpand},
+    expansion::expand_in_path,
+    signature::Signature,
+    typecheck::{target_points, typecheck, typecheck_cospan, TypeError},
+    Cospan, DiagramN, Rewrite,
+};
+
+#[derive(Debug, Error)]
+pub enum ExcisionError {
+    #[error("there is no top-dimensional cell at the given point")]
+    NotACell,
+
+    #[error("the cell cannot be moved to the boundary of the diagram")]
+    NotOnBoundary,
+
+    #[error("excision is ill-typed: {0}")]
+    IllTyped(#[from] TypeError),
+}
+
+impl DiagramN {
+    /// Remove the top-dimensional cell at a point of the diagram.
+    ///
+    /// The cell is moved to the top or the bottom level of the diagram by interchanging it with
+    /// the levels above or below it, which are then beheaded or befooted respectively. This
+    /// fails if the cell cannot be brought to the boundary in this way, for example because a
+    /// cell above and a cell below are both connected to it.
+    pub fn excise(
+        &self,
+        point: &[Height],
+        signature: &impl Signature,
+    ) -> Result<Self, ExcisionError> {
+        let point: Vec<SingularHeight> = point
+            .iter()
+            .map(|height| match height {
+                Height::Singular(h) => Some(*h),
+                Height::Regular(_) => None,
+            })
+            .collect::<Option<_>>()
+            .ok_or(ExcisionError::NotACell)?;
+
+        if point.len() != self.dimension()
+            || point[0] >= self.size()
+            || !cells(self, point[0]).contains(&point[1..].to_vec())
+        {
+            return Err(ExcisionError::NotACell);
+        }
+
+        let excised = [Direction::Forward, Direction::Backward]
+            .into_iter()
+            .find_map(|direction| {
+                let diagram = slide(self, point.clone(), direction, signature)?;
+                Some(match direction {
+                    Direction::Forward => diagram.behead(diagram.size() - 1),
+                    Direction::Backward => diagram.befoot(1),
+                })
+            })
+            .ok_or(ExcisionError::NotOnBoundary)?;
+
+        typecheck(&excised.clone().into(), signature, Mode::Shallow)?;
+
+        Ok(excised)
+    }
+}
+
+/// The points of the top-dimensional cells in the singular slice at the given height, relative to
+/// the slice.
+fn cells(diagram: &DiagramN, height: SingularHeight) -> Vec<Vec<SingularHeight>> {
+    let cospan = &diagram.cospans()[height];
+    target_points(&[cospan.forward.clone(), cospan.backward.clone()])
+        .into_iter()
+        .filter(|(_, generator)| generator.dimension == diagram.dimension())
+        .map(|(point, _)| point)
+        .collect()
+}
+
+/// The image of a point under the singular slices of a rewrite.
+fn point_image(rewrite: &Rewrite, point: &[SingularHeight]) -> Vec<SingularHeight> {
+    match (rewrite, point.split_first()) {
+        (Rewrite::RewriteN(rewrite), Some((&height, rest))) => {
+            std::iter::once(rewrite.singular_image(height))
+                .chain(point_image(&rewrite.slice(height), rest))
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Move the cell at a point to the top (forwards) or bottom (backwards) level of the diagram,
+/// one level at a time.
+fn slide(
+    diagram: &DiagramN,
+    mut point: Vec<SingularHeight>,
+    direction: Direction,
+    signature: &impl Signature,
+) -> Option<DiagramN> {
+    let mut diagram = diagram.clone();
+
+    if cells(&diagram, point[0]).len() > 1 {
+        (diagram, point) = separate(&diagram, &point, direction, signature)?;
+    }
+
+    loop {
+        let height = point[0];
+        match direction {
+            Direction::Forward if height + 1 == diagram.size() => return Some(diagram),
+            Direction::Backward if height == 0 => return Some(diagram),
+            _ => {}
+        }
+
+        // Merge the level of the cell with the next one and then separate it out on the other
+        // side.
+        (diagram, point) = [
+            None,
+            Some(Bias::Higher),
+            Some(Bias::Same),
+            Some(Bias::Lower),
+        ]
+        .into_iter()
+        .find_map(|bias| {
+            let ContractExpand { contract, expand } =
+                contract_in_path(&diagram, &mut [], height, direction, bias, false).ok()?;
+            if !expand.is_identity() {
+                return None;
+            }
+
+            let cospan = Cospan {
+                forward: contract.clone().into(),
+                backward: expand.into(),
+            };
+            typecheck_cospan(diagram.clone().into(), cospan, signature).ok()?;
+
+            let merged = diagram.clone().rewrite_forward(&contract).ok()?;
+            let mut merged_point = vec![contract.singular_image(height)];
+            merged_point.extend(point_image(&contract.slice(height), &point[1..]));
+
+            separate(&merged, &merged_point, direction, signature)
+        })?;
+    }
+}
+
+/// Expand the level of the cell at a point so that the cell is alone on the last (forwards) or
+/// first (backwards) of the resulting levels.
+fn separate(
+    diagram: &DiagramN,
+    point: &[SingularHeight],
+    direction: Direction,
+    signature: &impl Signature,
+) -> Option<(DiagramN, Vec<SingularHeight>)> {
+    if point.len() < 2 {
+        return None;
+    }
+
+    [Direction::Forward, Direction::Backward]
+        .into_iter()
+        .find_map(|expand_direction| {
+            let expand = expand_in_path(
+                diagram,
+                &mut [],
+                [Height::Singular(point[0]), Height::Singular(point[1])],
+                expand_direction,
+            )
+            .ok()?;
+
+            let cospan = Cospan {
+                forward: Rewrite::identity(diagram.dimension()),
+                backward: expand.clone().into(),
+            };
+            typecheck_cospan(diagram.clone().into(), cospan, signature).ok()?;
+
+            let expanded = diagram.clone().rewrite_backward(&expand).ok()?;
+            let preimage = expand.singular_preimage(point[0]);
+            let height = match direction {
+                Direction::Forward => preimage.end.checked_sub(1)?,
+                Direction::Backward => preimage.start,
+            };
+
+            let cells = cells(&expanded, height);
+            let [cell] = cells.as_slice() else {
+                return None;
+            };
+            if point_image(&expand.slice(height), cell) != point[1..] {
+                return None;
+            }
+
+            let mut expanded_point = vec![height];
+            expanded_point.extend(cell);
+            Some((expanded, expanded_point))
+        })
+}
@@ -3,12 +3,14 @@ use std::{
     convert::{Into, TryInto},
 };
 
+use itertools::Itertools;
 use thiserror::Error;
 
 use crate::{
     attach::attach,
     common::{
-        Boundary, BoundaryPath, DimensionError, Direction, Height, RegularHeight, SingularHeight,
+        Boundary, BoundaryPath, DimensionError, Direction, Height, Location, RegularHeight,
+        SingularHeight,
     },
     diagram::DiagramN,
     factorization::factorize,
@@ -37,16 +39,26 @@ pub enum ExpansionError {
     #[error("singular height is not smoothable")]
     Unsmoothable,
 
-    #[error("expansion failed to propagate")]
-    FailedToPropagate,
+    #[error("expansion failed to propagate {0}")]
+    FailedToPropagate(Location),
 
-    #[error("expansion is ill-typed")]
+    #[error("expansion is ill-typed: {0}")]
     IllTyped(#[from] TypeError),
 
     #[error(transparent)]
     Dimension(#[from] DimensionError),
 }
 
+impl ExpansionError {
+    /// The same error, with its location seen from one level further up.
+    fn nested(self) -> Self {
+        match self {
+            Self::FailedToPropagate(location) => Self::FailedToPropagate(location.nested()),
+            error => error,
+        }
+    }
+}
+
 impl DiagramN {
     pub fn expand(
         &self,
@@ -96,7 +108,8 @@ pub fn expand_in_path(
                 .slice(*step)
                 .ok_or(ExpansionError::OutOfBounds)?
                 .try_into()?;
-            let recursive = expand_in_path(&slice, rest, point, direction)?;
+            let recursive =
+                expand_in_path(&slice, rest, point, direction).map_err(ExpansionError::nested)?;
             expand_propagate(diagram, step, recursive.into(), true)
         }
     }
@@ -480,14 +493,16 @@ pub(crate) fn expand_propagate(
         //             .unwrap(),
         //         &slice,
         //         &target_cospan.backward,
-        //     );
+        //     )
+        //     .unwrap();
         //     let (_, inner_backward, inner_forward) = antipushout(
         //         &slice.clone().rewrite_backward(&expansion).unwrap(),
         //         &slice.clone().rewrite_backward(&inclusion).unwrap(),
         //         &slice,
         //         &expansion,
         //         &inclusion,
-        //     )[0]
+        //     )
+        //     .unwrap()[0]
         //     .clone();
 
         //     Some(Cone::new(
@@ -515,14 +530,16 @@ pub(crate) fn expand_propagate(
         //             .unwrap(),
         //         &slice,
         //         &target_cospan.forward,
-        //     );
+        //     )
+        //     .unwrap();
         //     let (_, inner_backward, inner_forward) = antipushout(
         //         &slice.clone().rewrite_backward(&inclusion).unwrap(),
         //         &slice.clone().rewrite_backward(&expansion).unwrap(),
         //         &slice,
         //         &inclusion,
         //         &expansion,
-        //     )[0]
+        //     )
+        //     .unwrap()[0]
         //     .clone();
 
         //     Some(Cone::new(
@@ -571,7 +588,20 @@ pub(crate) fn expand_propagate(
                     vec![Rewrite::identity(diagram.dimension() - 1); 2],
                 ))
             } else {
-                return Err(ExpansionError::FailedToPropagate);
+                // The cones of the cospan which are not homotopies block the expansion.
+                let cones = [&target_cospan.forward, &target_cospan.backward]
+                    .into_iter()
+                    .filter_map(|rewrite| <&RewriteN>::try_from(rewrite).ok())
+                    .flat_map(RewriteN::cones)
+                    .filter(|cone| !cone.is_homotopy())
+                    .map(|cone| cone.index)
+                    .sorted()
+                    .dedup()
+                    .collect();
+                return Err(ExpansionError::FailedToPropagate(Location::new(
+                    vec![i],
+                    cones,
+                )));
             }
         }
     };
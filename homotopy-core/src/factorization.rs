@@ -6,7 +6,7 @@ use crate::{
     common::Mode,
     monotone::{MonotoneIterator, Split},
     rewrite::Cone,
-    Cospan, Diagram, Height, Rewrite, RewriteN,
+    Cospan, Diagram, DiagramN, Height, Orientation, Rewrite, Rewrite0, RewriteN,
 };
 
 /// Given `Rewrite`s A -f> C <g- B, find some `Rewrite` A -h> B which factorises f = g ∘ h.
@@ -210,15 +210,23 @@ impl Iterator for ConeFactorizationInternal {
 
 impl std::iter::FusedIterator for ConeFactorizationInternal {}
 
+/// Enumerates the well-formed cones with the given source and target, from the candidates for each
+/// of their slices, alternating between regular and singular slices.
 #[derive(Clone)]
-struct ConeIterator {
+struct ConeIterator<I = Factorization>
+where
+    I: Iterator<Item = Rewrite> + Clone,
+{
     index: usize,
     source: Vec<Cospan>,
     target: Cospan,
-    slices_product: MultiProduct<Factorization>,
+    slices_product: MultiProduct<I>,
 }
 
-impl Iterator for ConeIterator {
+impl<I> Iterator for ConeIterator<I>
+where
+    I: Iterator<Item = Rewrite> + Clone,
+{
     type Item = Cone;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -238,4 +246,133 @@ impl Iterator for ConeIterator {
     }
 }
 
-impl std::iter::FusedIterator for ConeIterator {}
+impl<I> std::iter::FusedIterator for ConeIterator<I> where I: Iterator<Item = Rewrite> + Clone {}
+
+/// Enumerate every rewrite from `source` to `target` in which each cone, in every dimension, has a
+/// source of at most `bound` levels.
+///
+/// A point can be rewritten to a point of higher dimension, or to the same generator with zero
+/// orientation. Rewrites of dimension 0 are constructed without labels, in the same way as
+/// composites, so the results should be compared with [`Rewrite::equals_modulo_labels`].
+pub fn rewrites(source: Diagram, target: Diagram, bound: usize) -> Rewrites {
+    match (source, target) {
+        (Diagram::Diagram0(s), Diagram::Diagram0(t)) => Rewrites::Unique(
+            (s == t
+                || s.generator.dimension < t.generator.dimension
+                || (s.generator == t.generator && t.orientation == Orientation::Zero))
+                .then(|| Rewrite0::new(s, t, None).into()),
+        ),
+        (Diagram::DiagramN(source), Diagram::DiagramN(target))
+            if source.dimension() == target.dimension() =>
+        {
+            if target.size() == 0 {
+                return Rewrites::Unique(
+                    (source.size() == 0).then(|| Rewrite::identity(source.dimension())),
+                );
+            }
+
+            let constraints = vec![0..target.size(); source.size()];
+            Rewrites::Iterator(RewritesInternal {
+                monotone: MonotoneIterator::new(false, &constraints),
+                source_slices: source.slices().collect(),
+                target_slices: target.slices().collect(),
+                source,
+                target,
+                bound,
+                cur: None,
+            })
+        }
+        _ => Rewrites::Unique(None),
+    }
+}
+
+#[derive(Clone)]
+pub enum Rewrites {
+    Unique(Option<Rewrite>),
+    Iterator(RewritesInternal),
+}
+
+impl Iterator for Rewrites {
+    type Item = Rewrite;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Unique(r) => r.take(),
+            Self::Iterator(rewrites) => rewrites.next(),
+        }
+    }
+}
+
+impl std::iter::FusedIterator for Rewrites {}
+
+#[derive(Clone)]
+pub struct RewritesInternal {
+    source: DiagramN,
+    target: DiagramN,
+    source_slices: Vec<Diagram>,
+    target_slices: Vec<Diagram>,
+    bound: usize,
+    monotone: MonotoneIterator,
+    cur: Option<MultiProduct<ConeIterator<Rewrites>>>,
+}
+
+impl RewritesInternal {
+    /// The cones over a singular height of the target with the given source levels.
+    fn cones(&self, Split { source, target }: Split) -> ConeIterator<Rewrites> {
+        let cospan = &self.target.cospans()[target];
+        let singular = &self.target_slices[usize::from(Height::Singular(target))];
+
+        let slices = (usize::from(Height::Regular(source.start))
+            ..=usize::from(Height::Regular(source.end)))
+            .map(|i| {
+                if i == usize::from(Height::Regular(source.start)) {
+                    Rewrites::Unique(Some(cospan.forward.clone()))
+                } else if i == usize::from(Height::Regular(source.end)) {
+                    Rewrites::Unique(Some(cospan.backward.clone()))
+                } else {
+                    rewrites(self.source_slices[i].clone(), singular.clone(), self.bound)
+                }
+            })
+            .multi_cartesian_product();
+
+        ConeIterator {
+            index: source.start,
+            source: self.source.cospans()[source].to_vec(),
+            target: cospan.clone(),
+            slices_product: slices,
+        }
+    }
+}
+
+impl Iterator for RewritesInternal {
+    type Item = Rewrite;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match &mut self.cur {
+                None => {
+                    let monotone = self.monotone.next()?;
+                    let splits: Vec<Split> = monotone.cones(self.target.size()).collect();
+                    if splits.iter().any(|split| split.source.len() > self.bound) {
+                        continue;
+                    }
+
+                    self.cur = Some(
+                        splits
+                            .into_iter()
+                            .map(|split| self.cones(split))
+                            .multi_cartesian_product(),
+                    );
+                }
+                Some(cones) => match cones.next() {
+                    None => self.cur = None,
+                    Some(cones) => {
+                        return Some(RewriteN::new(self.source.dimension(), cones).into())
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for RewritesInternal {}
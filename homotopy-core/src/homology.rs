@@ -0,0 +1,357 @@
+use homotopy_common::hash::{FastHashMap, FastHashSet};
+use thiserror::Error;
+
+use crate::{
+    common::{Generator, Orientation, SliceIndex},
+    mesh::Mesh,
+    signature::{GeneratorInfo, Signature},
+    Diagram,
+};
+
+/// The largest dimension of a diagram whose strata can be analysed.
+pub const MAX_DIMENSION: usize = 4;
+
+#[derive(Debug, Error)]
+pub enum HomologyError {
+    #[error("strata can only be computed for diagrams of dimension at most {MAX_DIMENSION}")]
+    Dimension,
+
+    #[error("the stratum of a generator can have dimension at most 2")]
+    StratumDimension,
+}
+
+/// Homology with rational coefficients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Homology {
+    /// The Betti numbers in each degree, up to the dimension of the stratum.
+    pub betti_numbers: Vec<usize>,
+}
+
+impl Homology {
+    pub fn euler_characteristic(&self) -> isize {
+        self.betti_numbers
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                if i % 2 == 0 {
+                    *b as isize
+                } else {
+                    -(*b as isize)
+                }
+            })
+            .sum()
+    }
+}
+
+/// A simplicial complex, given by its simplices in each dimension as sorted lists of vertices.
+type Complex = Vec<Vec<Vec<usize>>>;
+
+/// Compute the homology of the closure of the stratum of a generator in the geometric realisation
+/// of a diagram.
+///
+/// In a diagram of dimension `n`, the stratum of a generator of dimension `k` is the part of the
+/// `n`-cube labelled by the generator, which has dimension `n - k`. Its closure also contains the
+/// points of the strata of higher-dimensional generators lying on it, so for example the closure
+/// of the stratum of a wire which ends in a cap includes the cap.
+pub fn stratum_homology(
+    diagram: &Diagram,
+    generator: Generator,
+) -> Result<Homology, HomologyError> {
+    let Some(dimension) = diagram.dimension().checked_sub(generator.dimension) else {
+        return Ok(Homology {
+            betti_numbers: vec![],
+        });
+    };
+
+    if dimension > 2 {
+        return Err(HomologyError::StratumDimension);
+    }
+
+    let simplices = simplices(diagram, generator, dimension)?;
+    Ok(homology(&close(simplices, dimension)))
+}
+
+/// Compute the homology of the CW complex which has a cell for each generator of a signature,
+/// attached along the boundary of its diagram.
+///
+/// For example, a signature with a 0-cell, two 1-cells `f` and `g`, and a 2-cell from `f g` to
+/// `g f` presents the torus.
+pub fn signature_homology(signature: &impl Signature) -> Result<Homology, HomologyError> {
+    let mut cells: Vec<Vec<Generator>> = vec![];
+    for generator in signature.generators() {
+        if cells.len() <= generator.dimension {
+            cells.resize(generator.dimension + 1, vec![]);
+        }
+        cells[generator.dimension].push(generator);
+    }
+
+    let mut ranks = vec![0; cells.len()];
+    for n in 1..cells.len() {
+        let mut columns = vec![];
+        for cell in &cells[n] {
+            let info = signature.generator_info(*cell).unwrap();
+            let Diagram::DiagramN(diagram) = info.diagram() else {
+                continue;
+            };
+
+            // The attaching map covers each cell of the boundary once for every point of it in the
+            // target, and once in the opposite direction for every point of it in the source,
+            // where inverse points count negatively.
+            let mut column = vec![];
+            for (row, face) in cells[n - 1].iter().enumerate() {
+                let degree = degree(&diagram.target(), *face)? - degree(&diagram.source(), *face)?;
+                if degree != 0 {
+                    column.push((row, i128::from(degree)));
+                }
+            }
+            columns.push(column);
+        }
+        ranks[n] = rank(columns);
+    }
+
+    Ok(Homology {
+        betti_numbers: betti_numbers(cells.iter().map(Vec::len), &ranks),
+    })
+}
+
+fn simplices(
+    diagram: &Diagram,
+    generator: Generator,
+    dimension: usize,
+) -> Result<Vec<Vec<usize>>, HomologyError> {
+    Ok(match diagram.dimension() {
+        0 => stratum::<0>(diagram, generator, dimension),
+        1 => stratum::<1>(diagram, generator, dimension),
+        2 => stratum::<2>(diagram, generator, dimension),
+        3 => stratum::<3>(diagram, generator, dimension),
+        4 => stratum::<4>(diagram, generator, dimension),
+        _ => return Err(HomologyError::Dimension),
+    })
+}
+
+/// The number of points of a generator in a diagram of the same dimension, counted with their
+/// orientation.
+fn degree(diagram: &Diagram, generator: Generator) -> Result<i64, HomologyError> {
+    Ok(match diagram.dimension() {
+        0 => points::<0>(diagram, generator),
+        1 => points::<1>(diagram, generator),
+        2 => points::<2>(diagram, generator),
+        3 => points::<3>(diagram, generator),
+        4 => points::<4>(diagram, generator),
+        _ => return Err(HomologyError::Dimension),
+    })
+}
+
+fn points<const N: usize>(diagram: &Diagram, generator: Generator) -> i64 {
+    let mesh = Mesh::<N>::new(diagram).unwrap();
+
+    let labels: FastHashMap<[SliceIndex; N], Orientation> = mesh
+        .nodes()
+        .filter_map(|(coord, diagram)| {
+            let point = diagram.max_generator();
+            (point.generator == generator).then_some((coord, point.orientation))
+        })
+        .collect();
+
+    mesh.cubes()
+        .filter(|cube| cube.visible && cube.dimension() == 0)
+        .filter_map(|cube| labels.get(&cube[0]))
+        .map(|orientation| match orientation {
+            Orientation::Negative => -1,
+            Orientation::Zero => 0,
+            Orientation::Positive => 1,
+        })
+        .sum()
+}
+
+/// The top-dimensional simplices of the stratum of a generator, obtained by triangulating the
+/// cubes of the mesh of the diagram in the same way as [`crate::complex::make_complex`].
+fn stratum<const N: usize>(
+    diagram: &Diagram,
+    generator: Generator,
+    dimension: usize,
+) -> Vec<Vec<usize>> {
+    const TRI_ASSEMBLY_ORDER: [[usize; 3]; 2] = [[0, 1, 3], [0, 3, 2]];
+
+    let mesh = Mesh::<N>::new(diagram).unwrap();
+
+    let mut vertices: FastHashMap<[SliceIndex; N], usize> = FastHashMap::default();
+    let mut labels: FastHashMap<[SliceIndex; N], Generator> = FastHashMap::default();
+    for (coord, diagram) in mesh.nodes() {
+        let len = vertices.len();
+        vertices.insert(coord, len);
+        labels.insert(coord, diagram.max_generator().generator);
+    }
+
+    let mut simplices = vec![];
+    for cube in mesh.cubes() {
+        if !cube.visible || cube.dimension() != dimension {
+            continue;
+        }
+
+        // The interior of a cube lies in the stratum of lowest codimension among its vertices.
+        let label = cube
+            .points
+            .iter()
+            .map(|point| labels[point])
+            .min_by_key(|generator| generator.dimension)
+            .unwrap();
+        if label != generator {
+            continue;
+        }
+
+        let faces: &[&[usize]] = match dimension {
+            0 => &[&[0]],
+            1 => &[&[0, 1]],
+            _ => &[&TRI_ASSEMBLY_ORDER[0], &TRI_ASSEMBLY_ORDER[1]],
+        };
+
+        for face in faces {
+            let mut simplex: Vec<usize> = face.iter().map(|i| vertices[&cube[*i]]).collect();
+            simplex.sort_unstable();
+            simplex.dedup();
+            if simplex.len() == face.len() {
+                simplices.push(simplex);
+            }
+        }
+    }
+    simplices
+}
+
+/// Close a set of simplices of the given dimension under taking faces.
+fn close(simplices: Vec<Vec<usize>>, dimension: usize) -> Complex {
+    let mut complex: Vec<FastHashSet<Vec<usize>>> = vec![FastHashSet::default(); dimension + 1];
+    complex[dimension].extend(simplices);
+    for i in (1..=dimension).rev() {
+        let (lower, upper) = complex.split_at_mut(i);
+        for simplex in &upper[0] {
+            for j in 0..simplex.len() {
+                let mut face = simplex.clone();
+                face.remove(j);
+                lower[i - 1].insert(face);
+            }
+        }
+    }
+
+    complex
+        .into_iter()
+        .map(|simplices| {
+            let mut simplices: Vec<_> = simplices.into_iter().collect();
+            simplices.sort_unstable();
+            simplices
+        })
+        .collect()
+}
+
+fn homology(complex: &Complex) -> Homology {
+    // The rank of the boundary map from each dimension to the one below it.
+    let ranks: Vec<usize> = (0..complex.len())
+        .map(|i| {
+            if i == 0 {
+                0
+            } else {
+                boundary_rank(&complex[i - 1], &complex[i])
+            }
+        })
+        .collect();
+
+    Homology {
+        betti_numbers: betti_numbers(complex.iter().map(Vec::len), &ranks),
+    }
+}
+
+/// The Betti numbers of a chain complex with the given number of generators in each degree and
+/// the given ranks of the boundary maps out of each degree.
+fn betti_numbers(sizes: impl Iterator<Item = usize>, ranks: &[usize]) -> Vec<usize> {
+    sizes
+        .enumerate()
+        .map(|(i, size)| size - ranks[i] - ranks.get(i + 1).copied().unwrap_or_default())
+        .collect()
+}
+
+/// The rank of the boundary map from `simplices` to their `faces`, in which the face opposite the
+/// `j`th vertex of a simplex has sign `(-1)^j`.
+fn boundary_rank(faces: &[Vec<usize>], simplices: &[Vec<usize>]) -> usize {
+    let index: FastHashMap<&[usize], usize> = faces
+        .iter()
+        .enumerate()
+        .map(|(i, face)| (face.as_slice(), i))
+        .collect();
+
+    rank(simplices.iter().map(|simplex| {
+        let mut column: Vec<(usize, i128)> = (0..simplex.len())
+            .map(|j| {
+                let mut face = simplex.clone();
+                face.remove(j);
+                (index[face.as_slice()], if j % 2 == 0 { 1 } else { -1 })
+            })
+            .collect();
+        column.sort_unstable();
+        column
+    }))
+}
+
+/// The rank over the rationals of an integer matrix given by the non-zero entries of each column,
+/// sorted by row, computed by reducing the columns until their lowest entries are distinct.
+///
+/// Columns are kept integral by cross-multiplying during reduction and dividing out the greatest
+/// common divisor of their entries afterwards.
+fn rank(columns: impl IntoIterator<Item = Vec<(usize, i128)>>) -> usize {
+    let mut pivots: FastHashMap<usize, Vec<(usize, i128)>> = FastHashMap::default();
+    for mut column in columns {
+        while let Some(&(low, a)) = column.last() {
+            let Some(pivot) = pivots.get(&low) else {
+                pivots.insert(low, column);
+                break;
+            };
+            let b = pivot.last().unwrap().1;
+            column = combine(&column, b, pivot, -a);
+        }
+    }
+
+    pivots.len()
+}
+
+/// The primitive integer column proportional to `x * a + y * b`.
+#[allow(clippy::many_single_char_names)]
+fn combine(a: &[(usize, i128)], x: i128, b: &[(usize, i128)], y: i128) -> Vec<(usize, i128)> {
+    let mut result = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let row_a = a.get(i).map_or(usize::MAX, |entry| entry.0);
+        let row_b = b.get(j).map_or(usize::MAX, |entry| entry.0);
+        let (row, value) = match row_a.cmp(&row_b) {
+            std::cmp::Ordering::Less => {
+                i += 1;
+                (row_a, x * a[i - 1].1)
+            }
+            std::cmp::Ordering::Greater => {
+                j += 1;
+                (row_b, y * b[j - 1].1)
+            }
+            std::cmp::Ordering::Equal => {
+                i += 1;
+                j += 1;
+                (row_a, x * a[i - 1].1 + y * b[j - 1].1)
+            }
+        };
+        if value != 0 {
+            result.push((row, value));
+        }
+    }
+
+    let divisor = result
+        .iter()
+        .fold(0, |divisor, &(_, value)| gcd(divisor, value.abs()));
+    for entry in &mut result {
+        entry.1 /= divisor;
+    }
+    result
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
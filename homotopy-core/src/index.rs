@@ -0,0 +1,117 @@
+use homotopy_common::hash::{FastHashMap, FastHashSet};
+
+use crate::{common::RegularHeight, Diagram, Diagram0};
+
+/// An index over a collection of diagrams, each stored under a key, for finding all the ways in
+/// which they embed into a given diagram.
+///
+/// A diagram can only embed into another one if every point it is made of also occurs in the
+/// other. The index remembers the points of each diagram and files it under its point of highest
+/// dimension, so a lookup only searches for the diagrams built out of points of the haystack
+/// instead of trying every diagram at every offset.
+#[derive(Debug, Clone)]
+pub struct EmbeddingIndex<K> {
+    entries: Vec<Entry<K>>,
+    buckets: FastHashMap<Diagram0, Vec<usize>>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<K> {
+    key: K,
+    diagram: Diagram,
+    points: FastHashSet<Diagram0>,
+}
+
+impl<K> Default for EmbeddingIndex<K> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::default(),
+            buckets: FastHashMap::default(),
+        }
+    }
+}
+
+impl<K> EmbeddingIndex<K> {
+    pub fn insert(&mut self, key: K, diagram: Diagram) {
+        let points = points(&diagram);
+        let bucket = points
+            .iter()
+            .copied()
+            .max_by_key(|point| (point.generator.dimension, point.generator.id))
+            .unwrap();
+        self.buckets
+            .entry(bucket)
+            .or_default()
+            .push(self.entries.len());
+        self.entries.push(Entry {
+            key,
+            diagram,
+            points,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find the embeddings of the indexed diagrams into `haystack`, in the same form as
+    /// [`Diagram::embeddings`]. Diagrams are visited in the order in which they were inserted.
+    pub fn embeddings<'a>(
+        &'a self,
+        haystack: &Diagram,
+    ) -> impl Iterator<Item = (&'a K, Vec<RegularHeight>)> + 'a {
+        let points = points(haystack);
+        let mut candidates: Vec<usize> = points
+            .iter()
+            .filter_map(|point| self.buckets.get(point))
+            .flatten()
+            .copied()
+            .filter(|index| {
+                let entry = &self.entries[*index];
+                entry.diagram.dimension() <= haystack.dimension() && entry.points.is_subset(&points)
+            })
+            .collect();
+        candidates.sort_unstable();
+
+        let haystack = haystack.clone();
+        candidates.into_iter().flat_map(move |index| {
+            let entry = &self.entries[index];
+            haystack
+                .embeddings(&entry.diagram)
+                .map(move |embedding| (&entry.key, embedding))
+        })
+    }
+}
+
+impl<K> Extend<(K, Diagram)> for EmbeddingIndex<K> {
+    fn extend<T: IntoIterator<Item = (K, Diagram)>>(&mut self, iter: T) {
+        for (key, diagram) in iter {
+            self.insert(key, diagram);
+        }
+    }
+}
+
+impl<K> FromIterator<(K, Diagram)> for EmbeddingIndex<K> {
+    fn from_iter<T: IntoIterator<Item = (K, Diagram)>>(iter: T) -> Self {
+        let mut index = Self::default();
+        index.extend(iter);
+        index
+    }
+}
+
+/// The points of a diagram, that is the generators it mentions together with their orientations.
+fn points(diagram: &Diagram) -> FastHashSet<Diagram0> {
+    diagram
+        .generators()
+        .into_iter()
+        .flat_map(|(generator, orientations)| {
+            orientations
+                .into_iter()
+                .map(move |orientation| Diagram0::new(generator, orientation))
+        })
+        .collect()
+}
@@ -0,0 +1,243 @@
+//! Invariants of knotted and braided wires, read off the 2-dimensional projection of a diagram.
+//!
+//! The wires in the projection of a 3- or 4-dimensional diagram pass each other at braids, which are
+//! the crossings of a knot or link diagram. Each crossing is given a sign by the usual right-hand
+//! rule, taking wires of positive orientation to point upwards: a crossing is positive if the
+//! strand in front goes from the bottom left to the top right.
+//!
+//! Only full braids are counted as crossings. The wires meeting at a half braid run on top of each
+//! other until the next one, so they are considered to be joined.
+use homotopy_common::hash::{FastHashMap, FastHashSet};
+use itertools::Itertools;
+use petgraph::unionfind::UnionFind;
+use thiserror::Error;
+
+use crate::{
+    common::{DimensionError, SingularHeight, SliceIndex},
+    complex::{make_complex, Simplex},
+    layout::Layout,
+    projection::{Depths, Projection},
+    Diagram, Height, Orientation,
+};
+
+type Coordinate = [SliceIndex; 2];
+
+#[derive(Debug, Error)]
+pub enum InvariantsError {
+    #[error(transparent)]
+    Dimension(#[from] DimensionError),
+
+    #[error("a point of the projection has no position in its layout")]
+    Layout,
+
+    #[error("a crossing of the projection is not between two of its wires")]
+    Crossing,
+}
+
+/// A crossing between two wires in the projection of a diagram.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SignedCrossing {
+    /// The height of the crossing.
+    pub height: SingularHeight,
+    /// The position of the left of the two crossing strands among the wires just below it.
+    pub index: usize,
+    /// The component of the wire in front.
+    pub over: usize,
+    /// The component of the wire behind.
+    pub under: usize,
+    /// Either `1` or `-1`.
+    pub sign: isize,
+}
+
+/// Knot and braid invariants of a diagram of dimension 2 to 4.
+///
+/// The wires of the projection are split into components, numbered from the bottom left, which
+/// join up through every point of the projection other than a crossing.
+#[derive(Clone, Debug)]
+pub struct Invariants {
+    crossings: Vec<SignedCrossing>,
+    components: usize,
+    braid_word: Option<Vec<isize>>,
+}
+
+impl Invariants {
+    pub fn new(diagram: &Diagram) -> Result<Self, InvariantsError> {
+        if !(2..=4).contains(&diagram.dimension()) {
+            return Err(DimensionError.into());
+        }
+
+        let layout = Layout::<2>::new(diagram)?;
+        let depths = Depths::<2>::new(diagram)?;
+        let projection = Projection::new(diagram, &layout, &depths)?;
+        let x = |point: &Coordinate| {
+            layout
+                .get(point)
+                .map(|position| position[0])
+                .ok_or(InvariantsError::Layout)
+        };
+
+        let at_crossing: FastHashSet<Coordinate> = projection
+            .crossings()
+            .iter()
+            .map(|crossing| crossing.coord)
+            .collect();
+        let wires = make_complex::<2>(diagram)
+            .into_iter()
+            .filter_map(|(simplex, visible)| match simplex {
+                Simplex::Wire(wire) if visible => Some(wire),
+                _ => None,
+            })
+            .collect_vec();
+
+        // Join the wires into components, cutting them at the crossings.
+        let points = wires
+            .iter()
+            .flatten()
+            .filter(|point| !at_crossing.contains(*point))
+            .copied()
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let index: FastHashMap<Coordinate, usize> = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| (*point, i))
+            .collect();
+        let mut union_find = UnionFind::new(points.len());
+        let mut join = |a: &Coordinate, b: &Coordinate| {
+            if let (Some(&a), Some(&b)) = (index.get(a), index.get(b)) {
+                union_find.union(a, b);
+            }
+        };
+        for [a, b] in &wires {
+            join(a, b);
+        }
+        for crossing in projection.crossings() {
+            join(&crossing.over[0], &crossing.over[1]);
+            join(&crossing.under[0], &crossing.under[1]);
+        }
+
+        let mut roots: FastHashMap<usize, usize> = FastHashMap::default();
+        for i in 0..points.len() {
+            let len = roots.len();
+            roots.entry(union_find.find(i)).or_insert(len);
+        }
+        let component = |point: &Coordinate| {
+            index
+                .get(point)
+                .map(|&i| roots[&union_find.find(i)])
+                .ok_or(InvariantsError::Crossing)
+        };
+
+        // The wires at every height which is not singular, ordered from left to right.
+        let mut levels: FastHashMap<SliceIndex, Vec<Coordinate>> = FastHashMap::default();
+        for point in &points {
+            if !matches!(point[0], SliceIndex::Interior(Height::Singular(_))) {
+                levels.entry(point[0]).or_default().push(*point);
+            }
+        }
+        for level in levels.values_mut() {
+            let mut positions = level
+                .iter()
+                .map(|point| Ok((x(point)?, *point)))
+                .collect::<Result<Vec<_>, InvariantsError>>()?;
+            positions.sort_by(|a, b| a.0.total_cmp(&b.0));
+            *level = positions.into_iter().map(|(_, point)| point).collect();
+        }
+
+        let direction = |point: &Coordinate| match projection.generator(*point).orientation {
+            Orientation::Negative => -1,
+            Orientation::Zero | Orientation::Positive => 1,
+        };
+
+        let mut adjacent = true;
+        let mut crossings = vec![];
+        for crossing in projection.crossings() {
+            let SliceIndex::Interior(Height::Singular(height)) = crossing.coord[0] else {
+                continue;
+            };
+            let below = levels
+                .get(&SliceIndex::Interior(Height::Regular(height)))
+                .ok_or(InvariantsError::Crossing)?;
+            let position = |point| {
+                below
+                    .iter()
+                    .position(|p| *p == point)
+                    .ok_or(InvariantsError::Crossing)
+            };
+            let [over, under] = [position(crossing.over[0])?, position(crossing.under[0])?];
+            adjacent &= over.abs_diff(under) == 1;
+
+            let sign = if over < under { 1 } else { -1 };
+            crossings.push((
+                sign,
+                SignedCrossing {
+                    height,
+                    index: over.min(under),
+                    over: component(&crossing.over[0])?,
+                    under: component(&crossing.under[0])?,
+                    sign: sign * direction(&crossing.over[0]) * direction(&crossing.under[0]),
+                },
+            ));
+        }
+        crossings.sort_by_key(|(_, crossing)| (crossing.height, crossing.index));
+
+        // The diagram is a braid if each component is a strand from the bottom to the top.
+        let strands = levels.values().map(Vec::len).unique().exactly_one().ok();
+        let braid_word =
+            (adjacent && strands.is_some_and(|strands| strands == roots.len())).then(|| {
+                crossings
+                    .iter()
+                    .map(|(sign, crossing)| sign * (crossing.index as isize + 1))
+                    .collect()
+            });
+
+        Ok(Self {
+            crossings: crossings
+                .into_iter()
+                .map(|(_, crossing)| crossing)
+                .collect(),
+            components: roots.len(),
+            braid_word,
+        })
+    }
+
+    /// The crossings of the diagram, from bottom to top and from left to right.
+    pub fn crossings(&self) -> &[SignedCrossing] {
+        &self.crossings
+    }
+
+    /// The number of connected components of the wires.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The sum of the signs of all crossings.
+    pub fn writhe(&self) -> isize {
+        self.crossings.iter().map(|crossing| crossing.sign).sum()
+    }
+
+    /// Half the sum of the signs of the crossings between two distinct components. This is an
+    /// integer when both components are closed loops.
+    pub fn linking_number(&self, a: usize, b: usize) -> f64 {
+        let sum: isize = self
+            .crossings
+            .iter()
+            .filter(|crossing| {
+                (crossing.over, crossing.under) == (a, b)
+                    || (crossing.over, crossing.under) == (b, a)
+            })
+            .map(|crossing| crossing.sign)
+            .sum();
+        sum as f64 / 2.0
+    }
+
+    /// The word in the Artin generators of the braid group presented by the diagram, if its
+    /// components are strands going from the bottom to the top which only cross their neighbours.
+    ///
+    /// The generator `i` stands for the strands at positions `i - 1` and `i` crossing with the left
+    /// one in front, and `-i` for its inverse.
+    pub fn braid_word(&self) -> Option<&[isize]> {
+        self.braid_word.as_deref()
+    }
+}
@@ -0,0 +1,419 @@
+//! Knots and links built from planar diagram (PD) codes and Gauss codes, as found in knot tables.
+//!
+//! A knot is a 4-dimensional diagram in a suspended signature, whose strands are copies of an
+//! invertible 3-dimensional point, such as the suspension of the scalar of `knotted_sphere.hom`
+//! and `figure_8_isotopy.hom`. Its wires are the strands of the knot. The diagram is obtained by
+//! sweeping the planar diagram from the bottom to the top: strands are born in pairs at cups, pass
+//! each other at braids, and annihilate in pairs at caps.
+use thiserror::Error;
+
+use crate::{
+    braid::crossing,
+    contraction::ContractionError,
+    diagram::AttachmentError,
+    signature::{GeneratorInfo, Signature},
+    Boundary, DiagramN,
+};
+
+/// A crossing `X[a, b, c, d]` of a PD code, whose four edges are listed counterclockwise starting
+/// from the incoming edge of the strand passing underneath.
+pub type Crossing = [usize; 4];
+
+#[derive(Debug, Error)]
+pub enum KnotError {
+    #[error("invalid code: {0}")]
+    Parse(String),
+
+    #[error("edge {0} does not join up two crossings consistently")]
+    Edge(usize),
+
+    #[error("the code does not describe a planar diagram")]
+    NonPlanar,
+
+    #[error("the strands of a knot must be copies of an invertible point of dimension 3")]
+    Strand,
+
+    #[error("failed to attach a cup or cap: {0}")]
+    Attachment(#[from] AttachmentError),
+
+    #[error("failed to build a crossing: {0}")]
+    Contraction(#[from] ContractionError),
+}
+
+/// Parse a PD code such as `X[1,4,2,5], X[3,6,4,1], X[5,2,6,3]`, by reading its edge labels in
+/// groups of four.
+pub fn parse_pd(code: &str) -> Result<Vec<Crossing>, KnotError> {
+    let labels = code
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|label| !label.is_empty())
+        .map(|label| {
+            label
+                .parse()
+                .map_err(|_err| KnotError::Parse(label.to_owned()))
+        })
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    if labels.len() % 4 != 0 {
+        return Err(KnotError::Parse(code.to_owned()));
+    }
+
+    Ok(labels
+        .chunks(4)
+        .map(|chunk| [chunk[0], chunk[1], chunk[2], chunk[3]])
+        .collect())
+}
+
+/// Parse the signed Gauss code of a knot, such as `O1-U2-O3-U1-O2-U3-`, and convert it into a PD
+/// code. Each passage through a crossing is given by whether the knot goes over or under, the
+/// number of the crossing and its sign.
+pub fn parse_gauss(code: &str) -> Result<Vec<Crossing>, KnotError> {
+    let invalid = || KnotError::Parse(code.to_owned());
+
+    let mut passages = vec![];
+    let mut chars = code.chars().filter(|c| !c.is_whitespace() && *c != ',');
+    while let Some(c) = chars.next() {
+        let over = match c.to_ascii_uppercase() {
+            'O' => true,
+            'U' => false,
+            _ => return Err(invalid()),
+        };
+        let mut number = String::new();
+        let sign = loop {
+            match chars.next() {
+                Some(c) if c.is_ascii_digit() => number.push(c),
+                Some('+') => break true,
+                Some('-') => break false,
+                _ => return Err(invalid()),
+            }
+        };
+        let number: usize = number.parse().map_err(|_err| invalid())?;
+        passages.push((over, number, sign));
+    }
+
+    let mut numbers: Vec<usize> = passages.iter().map(|(_, number, _)| *number).collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+
+    // The edge leaving passage `i` is labelled `i + 1`, so the edge arriving at it is `i`, or the
+    // last edge for the first passage.
+    let len = passages.len();
+    let mut crossings = vec![];
+    for number in numbers {
+        let mut under = None;
+        let mut over = None;
+        for (i, (is_over, n, sign)) in passages.iter().enumerate() {
+            if *n != number {
+                continue;
+            }
+            let edges = (if i == 0 { len } else { i }, i + 1);
+            let passage = if *is_over { &mut over } else { &mut under };
+            if passage.replace((edges, *sign)).is_some() {
+                return Err(invalid());
+            }
+        }
+
+        let (Some(((a, c), under_sign)), Some(((incoming, outgoing), over_sign))) = (under, over)
+        else {
+            return Err(invalid());
+        };
+        if under_sign != over_sign {
+            return Err(invalid());
+        }
+
+        // At a positive crossing, the strand on top passes from the left to the right of the
+        // strand underneath.
+        crossings.push(if over_sign {
+            [a, outgoing, c, incoming]
+        } else {
+            [a, incoming, c, outgoing]
+        });
+    }
+
+    Ok(crossings)
+}
+
+/// Construct the 4-dimensional diagram of the knot or link with a given PD code, whose strands
+/// are copies of `strand`. The strand must be an invertible point of dimension 3, i.e. a cell
+/// between equal diagrams, such as a scalar in a suspended signature. The empty code gives the
+/// unknot.
+pub fn knot(
+    strand: &DiagramN,
+    code: &[Crossing],
+    signature: &impl Signature,
+) -> Result<DiagramN, KnotError> {
+    let invertible = signature
+        .generator_info(strand.max_generator().generator)
+        .is_some_and(GeneratorInfo::is_invertible);
+    if strand.dimension() != 3 || strand.source() != strand.target() || !invertible {
+        return Err(KnotError::Strand);
+    }
+
+    let incoming = orientations(code)?;
+
+    // Whether the sweep gets stuck depends on which face of the planar diagram ends up outside,
+    // so try starting from every crossing in every rotation.
+    let steps = if code.is_empty() {
+        vec![Step::Cup(0, true), Step::Cap(0)]
+    } else {
+        (0..code.len())
+            .flat_map(|x| (0..4).map(move |r| (x, r)))
+            .find_map(|start| plan(code, &incoming, start))
+            .ok_or(KnotError::NonPlanar)?
+    };
+
+    let point = |up| {
+        if up {
+            strand.clone()
+        } else {
+            strand.inverse()
+        }
+    };
+
+    let mut diagram = strand.source().identity().identity();
+    let mut frontier: Vec<bool> = vec![];
+    for step in steps {
+        let (piece, position) = match step {
+            Step::Cup(position, left_up) => {
+                frontier.splice(position..position, [left_up, !left_up]);
+                (point(left_up).bubble(), position)
+            }
+            Step::Cap(position) => {
+                let left_up = frontier[position];
+                frontier.drain(position..position + 2);
+                (point(left_up).bubble().inverse(), position)
+            }
+            Step::Cross(position, positive) => {
+                let slice = point(frontier[position]).attach(
+                    &point(frontier[position + 1]),
+                    Boundary::Target,
+                    &[],
+                )?;
+                let cospan = crossing(&slice, 0, positive, signature)?;
+                frontier.swap(position, position + 1);
+                (DiagramN::new(slice.into(), vec![cospan]), position)
+            }
+        };
+        diagram = diagram.attach(&piece, Boundary::Target, &[position])?;
+    }
+
+    Ok(diagram)
+}
+
+/// Determine for each edge of each crossing whether the knot enters the crossing through it.
+///
+/// The strand underneath enters through the first edge. The direction of the strand on top is
+/// propagated from the other ends of its edges where possible, and otherwise read off from the
+/// consecutive numbering of the edges along each component.
+fn orientations(code: &[Crossing]) -> Result<Vec<[bool; 4]>, KnotError> {
+    let ends = |edge: usize| {
+        code.iter().enumerate().flat_map(move |(x, crossing)| {
+            (0..4)
+                .filter(move |&i| crossing[i] == edge)
+                .map(move |i| (x, i))
+        })
+    };
+    for crossing in code {
+        for edge in crossing {
+            if ends(*edge).count() != 2 {
+                return Err(KnotError::Edge(*edge));
+            }
+        }
+    }
+
+    let mut incoming: Vec<[Option<bool>; 4]> = code
+        .iter()
+        .map(|_| [Some(true), None, Some(false), None])
+        .collect();
+    loop {
+        let mut changed = false;
+        for x in 0..code.len() {
+            for i in [1, 3] {
+                if incoming[x][i].is_some() {
+                    continue;
+                }
+                let other = ends(code[x][i]).find(|end| *end != (x, i)).unwrap();
+                if let Some(other) = incoming[other.0][other.1] {
+                    incoming[x][i] = Some(!other);
+                    incoming[x][(i + 2) % 4] = Some(other);
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            continue;
+        }
+
+        // Guess the direction of the next strand on top from the numbering of its edges.
+        let Some(x) = (0..code.len()).find(|&x| incoming[x][1].is_none()) else {
+            break;
+        };
+        let [_, b, _, d] = code[x];
+        let forward = d == b + 1 || b > d + 1;
+        incoming[x][1] = Some(forward);
+        incoming[x][3] = Some(!forward);
+    }
+
+    let incoming: Vec<[bool; 4]> = incoming
+        .into_iter()
+        .map(|flags| flags.map(Option::unwrap))
+        .collect();
+
+    for crossing in code {
+        for edge in crossing {
+            if ends(*edge).filter(|(x, i)| incoming[*x][*i]).count() != 1 {
+                return Err(KnotError::Edge(*edge));
+            }
+        }
+    }
+
+    Ok(incoming)
+}
+
+/// A step of a sweep through a planar diagram, acting on the strands at a position.
+#[derive(Copy, Clone, Debug)]
+enum Step {
+    /// Create a pair of strands, of which the left one points upwards if the flag is set.
+    Cup(usize, bool),
+    /// Annihilate a pair of strands.
+    Cap(usize),
+    /// Let two strands cross, with the left one in front if the flag is set.
+    Cross(usize, bool),
+}
+
+/// Plan a sweep through a planar diagram from the bottom to the top, starting with a crossing in
+/// a given rotation.
+fn plan(code: &[Crossing], incoming: &[[bool; 4]], start: (usize, usize)) -> Option<Vec<Step>> {
+    let (x, r) = start;
+    if code[x][r] == code[x][(r + 1) % 4] {
+        return None;
+    }
+
+    let mut sweep = Sweep::default();
+    let mut done = vec![false; code.len()];
+    let mut next = Some((x, r, 0));
+    while let Some((x, r, k)) = next {
+        done[x] = true;
+
+        // Rotate the crossing so that its first two edges arrive from below.
+        let edge = |i: usize| code[x][(r + i) % 4];
+        let incoming = |i: usize| incoming[x][(r + i) % 4];
+        let position = if k == 0 {
+            sweep.frontier.len()
+        } else {
+            sweep.position(edge(0))?
+        };
+
+        // Create the strands arriving from below which are not on the frontier yet.
+        if k == 0 {
+            sweep.cup(position, edge(0), !incoming(0));
+            sweep.cup(position + 2, edge(1), incoming(1));
+            sweep.cross(position + 1, r % 2 == 1, [edge(3), edge(2)]);
+        } else {
+            if sweep.position(edge(1)).is_none() {
+                sweep.cup(position + 1, edge(1), incoming(1));
+            }
+            sweep.cross(position, r % 2 == 1, [edge(3), edge(2)]);
+        }
+
+        // Close up the strands which have reached their other end.
+        while let Some(i) =
+            (1..sweep.frontier.len()).find(|&i| sweep.frontier[i - 1].0 == sweep.frontier[i].0)
+        {
+            sweep.cap(i - 1);
+        }
+
+        next = sweep.next(code, &done);
+    }
+
+    (sweep.frontier.is_empty() && !done.contains(&false)).then_some(sweep.steps)
+}
+
+/// The state of a sweep through a planar diagram, made up of the steps taken so far and the
+/// edges crossing the top of the part swept so far, from left to right, together with whether
+/// they point upwards.
+#[derive(Default)]
+struct Sweep {
+    steps: Vec<Step>,
+    frontier: Vec<(usize, bool)>,
+}
+
+impl Sweep {
+    fn position(&self, edge: usize) -> Option<usize> {
+        self.frontier.iter().position(|(e, _)| *e == edge)
+    }
+
+    /// Find the next crossing to add together with the rotation in which it should be added and
+    /// the number of its edges on the frontier. The edges on the frontier must be next to each
+    /// other both around the crossing and on the frontier. Returns `None` if the sweep is finished
+    /// or stuck.
+    fn next(&self, code: &[Crossing], done: &[bool]) -> Option<(usize, usize, usize)> {
+        let mut best: Option<(usize, usize, usize)> = None;
+        let mut touching = false;
+        for (x, crossing) in code.iter().enumerate() {
+            if done[x] {
+                continue;
+            }
+
+            let on_frontier = crossing.map(|edge| self.position(edge));
+            let k = on_frontier.iter().flatten().count();
+            touching |= k > 0;
+            if best.is_some_and(|(_, _, best)| best >= k) {
+                continue;
+            }
+
+            let rotation = (0..4).find(|&r| {
+                if k == 0 {
+                    // Avoid splitting up an edge which loops back to the crossing.
+                    return crossing[r] != crossing[(r + 1) % 4];
+                }
+                let Some(start) = on_frontier[r] else {
+                    return false;
+                };
+
+                // An edge looping back to the crossing may sit between its edges on the frontier,
+                // since it can be created together with the crossing.
+                (0..4)
+                    .map(|i| (r + i) % 4)
+                    .filter(|&i| crossing.iter().filter(|&&edge| edge == crossing[i]).count() == 1)
+                    .enumerate()
+                    .all(|(i, slot)| {
+                        if i < k {
+                            on_frontier[slot] == Some(start + i)
+                        } else {
+                            on_frontier[slot].is_none()
+                        }
+                    })
+            });
+
+            if let Some(r) = rotation {
+                best = Some((x, r, k));
+            }
+        }
+
+        // A new component can only be started once the current one has been closed up.
+        best.filter(|(_, _, k)| *k > 0 || !touching)
+    }
+
+    fn cup(&mut self, position: usize, edge: usize, left_up: bool) {
+        self.steps.push(Step::Cup(position, left_up));
+        self.frontier
+            .splice(position..position, [(edge, left_up), (edge, !left_up)]);
+    }
+
+    fn cap(&mut self, position: usize) {
+        self.steps.push(Step::Cap(position));
+        self.frontier.drain(position..position + 2);
+    }
+
+    /// Let the strands at a position of the frontier and the one after it cross, continuing them
+    /// as the given edges.
+    fn cross(&mut self, position: usize, positive: bool, edges: [usize; 2]) {
+        self.steps.push(Step::Cross(position, positive));
+        let [(_, left_up), (_, right_up)] = [self.frontier[position], self.frontier[position + 1]];
+        self.frontier.splice(
+            position..position + 2,
+            [(edges[0], right_up), (edges[1], left_up)],
+        );
+    }
+}
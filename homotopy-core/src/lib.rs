@@ -2,33 +2,41 @@ pub use common::{Boundary, Direction, Generator, Height, Orientation, SliceIndex
 pub use contraction::Bias;
 pub use diagram::{Diagram, Diagram0, DiagramN};
 pub use rewrite::{Cospan, Rewrite, Rewrite0, RewriteN};
+pub use session::collect_garbage;
 
 pub mod antipushout;
 pub mod attach;
+pub mod braid;
 pub mod bubble;
 pub mod check;
+pub mod colimit;
 pub mod collapse;
 pub mod common;
 pub mod complex;
 pub mod contraction;
+pub mod crop;
+pub mod desuspension;
 pub mod diagram;
 pub mod examples;
+pub mod excision;
 pub mod expansion;
 pub mod factorization;
+pub mod homology;
+pub mod index;
+pub mod invariants;
+pub mod knot;
 pub mod layout;
 pub mod mesh;
 pub mod migration;
 pub mod monotone;
+pub mod morphism;
+pub mod normalization;
 pub mod projection;
 pub mod rewrite;
 pub mod scaffold;
+pub mod search;
 pub mod serialize;
+pub mod session;
 pub mod signature;
+pub mod tracking;
 pub mod typecheck;
-
-pub fn collect_garbage() {
-    DiagramN::collect_garbage();
-    RewriteN::collect_garbage();
-    rewrite::Cone::collect_garbage();
-    common::Label::collect_garbage();
-}
@@ -0,0 +1,471 @@
+use homotopy_common::hash::FastHashMap;
+use thiserror::Error;
+
+use crate::{
+    common::{Boundary, Generator, Height, Mode, RegularHeight},
+    rewrite::Cone,
+    search::{search, Budget, SearchError},
+    signature::{GeneratorInfo, Signature, SignatureBuilder},
+    typecheck::{target_points, typecheck},
+    Cospan, Diagram, Diagram0, DiagramN, Rewrite, Rewrite0, RewriteN,
+};
+
+#[derive(Debug, Error)]
+pub enum MorphismError {
+    #[error("generator {0:?} is not in the signature")]
+    UnknownGenerator(Generator),
+
+    #[error("image of {0:?} is not a well-typed diagram of the same type")]
+    IllTyped(Generator),
+
+    #[error(
+        "{0:?} can only be substituted where it is the only top-dimensional cell on its level"
+    )]
+    Unsupported(Generator),
+
+    #[error("failed to find the image of a homotopy: {0}")]
+    Homotopy(#[from] SearchError),
+}
+
+/// The image of a generator of the source signature.
+#[derive(Clone, Debug)]
+struct Image {
+    /// The diagram of the generator in the source signature.
+    source: Diagram,
+    /// The image of the diagram in the target signature.
+    target: Diagram,
+    /// The generator of the target signature the generator is sent to, if any.
+    generator: Option<Generator>,
+    /// Whether the image is obtained by renaming the generators of the diagram, in which case the
+    /// generator can be renamed wherever it appears.
+    renamed: bool,
+}
+
+/// The images of diagrams, together with the regular height of the image which each regular
+/// height of a diagram is sent to.
+type Cache = FastHashMap<DiagramN, (DiagramN, Vec<RegularHeight>)>;
+
+/// A map sending generators of one signature to diagrams of the same type in another, applied
+/// functorially to diagrams, rewrites and signatures.
+///
+/// Generators which are not mapped explicitly are carried over to the target signature, with their
+/// diagrams translated into it. A generator which the target shares with the source, with the same
+/// diagram, is retyped in place. Any other generator keeps its id if the target does not use it,
+/// and is given a fresh id otherwise. A generator can be sent to another generator in any
+/// dimension, which generalises [`Diagram::replace`], or to a composite diagram.
+///
+/// A diagram is translated one level at a time. Levels containing a single top-dimensional cell
+/// are replaced by the image of the cell whiskered into the same position, and levels without
+/// top-dimensional cells are replaced by a homotopy between the images of their regular slices,
+/// found by a bounded search. Levels containing several top-dimensional cells can only be renamed.
+#[derive(Clone, Debug, Default)]
+pub struct SignatureMorphism {
+    images: FastHashMap<Generator, Image>,
+    signature: SignatureBuilder,
+}
+
+impl SignatureMorphism {
+    /// Construct a morphism from the image of each generator, checking that each image is a
+    /// well-typed diagram in the target signature whose boundary is the image of the boundary of
+    /// the generator.
+    pub fn new(
+        source: &impl Signature,
+        target: &impl Signature,
+        images: impl IntoIterator<Item = (Generator, Diagram)>,
+    ) -> Result<Self, MorphismError> {
+        let images: FastHashMap<Generator, Diagram> = images.into_iter().collect();
+        if let Some(&generator) = images
+            .keys()
+            .find(|generator| source.generator_info(**generator).is_none())
+        {
+            return Err(MorphismError::UnknownGenerator(generator));
+        }
+
+        let mut morphism = Self::default();
+        for generator in target.generators() {
+            let info = target.generator_info(generator).unwrap();
+            morphism
+                .signature
+                .insert(generator, info.diagram().clone(), info.is_invertible());
+        }
+
+        // Boundaries are of lower dimension, so only need the images so far.
+        let mut generators: Vec<Generator> = source.generators().collect();
+        generators.sort_by_key(|generator| generator.dimension);
+
+        for generator in generators {
+            let info = source.generator_info(generator).unwrap();
+            let diagram = info.diagram();
+            let boundaries = match diagram {
+                Diagram::Diagram0(_) => None,
+                Diagram::DiagramN(diagram) => Some([
+                    morphism.apply(&diagram.source())?,
+                    morphism.apply(&diagram.target())?,
+                ]),
+            };
+
+            let carried = if images.contains_key(&generator)
+                || target
+                    .generator_info(generator)
+                    .is_some_and(|info| info.diagram() == diagram)
+                || morphism
+                    .signature
+                    .generators()
+                    .all(|g| g.id != generator.id)
+            {
+                generator
+            } else {
+                Generator::new(morphism.signature.next_id(), generator.dimension)
+            };
+
+            let image = match (images.get(&generator), boundaries) {
+                (None, None) => Diagram0::from(carried).into(),
+                (None, Some([s, t])) => DiagramN::from_generator(carried, s, t)
+                    .map_err(|_err| MorphismError::IllTyped(generator))?
+                    .into(),
+                (Some(image), boundaries) => {
+                    if image.dimension() != generator.dimension
+                        || typecheck(image, target, Mode::Deep).is_err()
+                    {
+                        return Err(MorphismError::IllTyped(generator));
+                    }
+                    if let (Diagram::DiagramN(image), Some([s, t])) = (image, boundaries) {
+                        if image.source() != s || image.target() != t {
+                            return Err(MorphismError::IllTyped(generator));
+                        }
+                    }
+                    image.clone()
+                }
+            };
+
+            let top = image.max_generator().generator;
+            let sent_to = if images.contains_key(&generator) {
+                morphism
+                    .signature
+                    .generator_info(top)
+                    .filter(|info| info.diagram() == &image)
+                    .map(|_| top)
+            } else {
+                morphism
+                    .signature
+                    .insert(carried, image.clone(), info.is_invertible());
+                Some(carried)
+            };
+
+            morphism.images.insert(
+                generator,
+                Image {
+                    source: diagram.clone(),
+                    target: image.clone(),
+                    generator: sent_to,
+                    renamed: false,
+                },
+            );
+            let renamed = sent_to.is_some() && morphism.rename_diagram(diagram) == image;
+            morphism.images.get_mut(&generator).unwrap().renamed = renamed;
+        }
+
+        Ok(morphism)
+    }
+
+    /// The generator of the target signature which a generator is sent to, if it is sent to a
+    /// generator.
+    pub fn generator_image(&self, generator: Generator) -> Option<Generator> {
+        self.images
+            .get(&generator)
+            .and_then(|image| image.generator)
+    }
+
+    /// The image of a generator, if it is sent to a generator.
+    fn rename(&self, generator: Generator) -> Generator {
+        self.generator_image(generator).unwrap_or(generator)
+    }
+
+    /// Whether every generator of a diagram can be renamed in place.
+    fn renames(&self, diagram: &Diagram) -> bool {
+        diagram
+            .generators()
+            .keys()
+            .all(|generator| self.images.get(generator).is_none_or(|image| image.renamed))
+    }
+
+    /// Apply the morphism to a diagram.
+    pub fn apply(&self, diagram: &Diagram) -> Result<Diagram, MorphismError> {
+        self.apply_cached(diagram, &mut Cache::default())
+    }
+
+    fn apply_cached(&self, diagram: &Diagram, cache: &mut Cache) -> Result<Diagram, MorphismError> {
+        match diagram {
+            Diagram::Diagram0(_) => Ok(self.rename_diagram(diagram)),
+            Diagram::DiagramN(diagram) => Ok(self.apply_n(diagram, cache)?.0.into()),
+        }
+    }
+
+    fn apply_n(
+        &self,
+        diagram: &DiagramN,
+        cache: &mut Cache,
+    ) -> Result<(DiagramN, Vec<RegularHeight>), MorphismError> {
+        if let Some(applied) = cache.get(diagram) {
+            return Ok(applied.clone());
+        }
+
+        let applied = if self.renames(&diagram.clone().into()) {
+            let renamed = diagram.map(|s| self.rename_diagram(s), |r| self.rename_rewrite(r));
+            (renamed, (0..=diagram.size()).collect())
+        } else {
+            let source = self.apply_cached(&diagram.source(), cache)?;
+            let regular_slices: Vec<Diagram> = diagram.regular_slices().collect();
+            let singular_slices: Vec<Diagram> = diagram.singular_slices().collect();
+
+            let mut image = source.clone();
+            let mut cospans = vec![];
+            let mut heights = vec![0];
+
+            for (i, cospan) in diagram.cospans().iter().enumerate() {
+                let target = self.apply_cached(&regular_slices[i + 1], cache)?;
+
+                if [
+                    &regular_slices[i],
+                    &singular_slices[i],
+                    &regular_slices[i + 1],
+                ]
+                .into_iter()
+                .all(|slice| self.renames(slice))
+                {
+                    cospans.push(cospan.map(|r| self.rename_rewrite(r)));
+                } else {
+                    cospans.extend(self.level(
+                        &regular_slices[i],
+                        cospan,
+                        &image,
+                        &target,
+                        cache,
+                    )?);
+                }
+
+                heights.push(cospans.len());
+                image = target;
+            }
+
+            (DiagramN::new(source, cospans), heights)
+        };
+
+        cache.insert(diagram.clone(), applied.clone());
+        Ok(applied)
+    }
+
+    /// The cospans of the image of a level of a diagram, given its source and the images of its
+    /// regular slices.
+    fn level(
+        &self,
+        source: &Diagram,
+        cospan: &Cospan,
+        image_source: &Diagram,
+        image_target: &Diagram,
+        cache: &mut Cache,
+    ) -> Result<Vec<Cospan>, MorphismError> {
+        let cells: Vec<Generator> =
+            target_points(&[cospan.forward.clone(), cospan.backward.clone()])
+                .into_iter()
+                .map(|(_, generator)| generator)
+                .filter(|generator| generator.dimension == source.dimension() + 1)
+                .collect();
+
+        match cells[..] {
+            [] => self.homotopy(image_source, image_target),
+            [generator] => self
+                .whisker(source, generator, cospan, image_source, cache)?
+                .filter(|whiskered| whiskered.target() == *image_target)
+                .map(|whiskered| whiskered.cospans().to_vec())
+                .ok_or(MorphismError::Unsupported(generator)),
+            [generator, ..] => Err(MorphismError::Unsupported(generator)),
+        }
+    }
+
+    /// Find the position of an instance of a generator at a level with the given source, and
+    /// whisker its image into the same position of the image of the source.
+    fn whisker(
+        &self,
+        source: &Diagram,
+        generator: Generator,
+        cospan: &Cospan,
+        image_source: &Diagram,
+        cache: &mut Cache,
+    ) -> Result<Option<DiagramN>, MorphismError> {
+        let image = self
+            .images
+            .get(&generator)
+            .ok_or(MorphismError::UnknownGenerator(generator))?;
+        let (Diagram::DiagramN(diagram), Diagram::DiagramN(image)) = (&image.source, &image.target)
+        else {
+            return Ok(None);
+        };
+
+        let candidates = [
+            (diagram.clone(), image.clone()),
+            (diagram.inverse(), image.inverse()),
+        ];
+
+        for (diagram, image) in candidates {
+            for embedding in source.embeddings(&diagram.source()) {
+                let Ok(instance) =
+                    source
+                        .clone()
+                        .identity()
+                        .attach(&diagram, Boundary::Target, &embedding)
+                else {
+                    continue;
+                };
+                if instance.cospans() != [cospan.clone()] {
+                    continue;
+                }
+
+                let embedding = self.translate(source, &embedding, cache)?;
+                if let Ok(whiskered) =
+                    image_source
+                        .clone()
+                        .identity()
+                        .attach(&image, Boundary::Target, &embedding)
+                {
+                    return Ok(Some(whiskered));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Translate an embedding into a diagram to the corresponding embedding into its image.
+    fn translate(
+        &self,
+        diagram: &Diagram,
+        embedding: &[RegularHeight],
+        cache: &mut Cache,
+    ) -> Result<Vec<RegularHeight>, MorphismError> {
+        let (Diagram::DiagramN(diagram), Some((&height, rest))) =
+            (diagram, embedding.split_first())
+        else {
+            return Ok(vec![]);
+        };
+
+        let (_, heights) = self.apply_n(diagram, cache)?;
+        let slice = diagram.slice(Height::Regular(height)).unwrap();
+
+        let mut translated = vec![heights[height]];
+        translated.extend(self.translate(&slice, rest, cache)?);
+        Ok(translated)
+    }
+
+    /// Find a homotopy between the images of the regular slices of a level without
+    /// top-dimensional cells, searching from either end.
+    fn homotopy(&self, source: &Diagram, target: &Diagram) -> Result<Vec<Cospan>, MorphismError> {
+        let (Diagram::DiagramN(source), Diagram::DiagramN(target)) = (source, target) else {
+            return Err(SearchError::Boundary.into());
+        };
+
+        let budget = Budget::default();
+        let replay = |from: &DiagramN, to: &DiagramN| -> Result<Vec<Cospan>, SearchError> {
+            let mut diagram = from.clone();
+            let mut cospans = vec![];
+            for m in search(from, to, &self.signature, &budget)? {
+                let homotopy = m
+                    .homotopy(&diagram, &self.signature)
+                    .ok_or(SearchError::NotFound)?;
+                cospans.extend(homotopy.cospans().iter().cloned());
+                diagram = homotopy
+                    .target()
+                    .try_into()
+                    .map_err(|_err| SearchError::NotFound)?;
+            }
+            Ok(cospans)
+        };
+
+        match replay(source, target) {
+            Ok(cospans) => Ok(cospans),
+            Err(SearchError::NotFound) => {
+                let cospans = replay(target, source)?;
+                Ok(cospans.into_iter().rev().map(Cospan::flip).collect())
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Apply the morphism to a rewrite. Generators in the rewrite can only be renamed.
+    pub fn apply_rewrite(&self, rewrite: &Rewrite) -> Result<Rewrite, MorphismError> {
+        self.check_rewrite(rewrite)?;
+        Ok(self.rename_rewrite(rewrite))
+    }
+
+    /// Apply the morphism to the source signature, giving the target signature together with the
+    /// generators of the source which are sent to themselves, with their diagrams translated.
+    ///
+    /// The images of well-typed diagrams are well-typed in this signature.
+    pub fn apply_signature(&self) -> SignatureBuilder {
+        self.signature.clone()
+    }
+
+    fn check_rewrite(&self, rewrite: &Rewrite) -> Result<(), MorphismError> {
+        let check = |generator: Generator| {
+            if self
+                .images
+                .get(&generator)
+                .is_none_or(|image| image.renamed)
+            {
+                Ok(())
+            } else {
+                Err(MorphismError::Unsupported(generator))
+            }
+        };
+
+        match rewrite {
+            Rewrite::Rewrite0(r) => {
+                for d in r.source().into_iter().chain(r.target()) {
+                    check(d.generator)?;
+                }
+                Ok(())
+            }
+            Rewrite::RewriteN(r) => {
+                for cone in r.cones() {
+                    for cospan in cone.source().iter().chain(Some(cone.target())) {
+                        self.check_rewrite(&cospan.forward)?;
+                        self.check_rewrite(&cospan.backward)?;
+                    }
+                    for slice in cone.regular_slices().iter().chain(cone.singular_slices()) {
+                        self.check_rewrite(slice)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn rename_diagram(&self, diagram: &Diagram) -> Diagram {
+        match diagram {
+            Diagram::Diagram0(d) => Diagram0::new(self.rename(d.generator), d.orientation).into(),
+            Diagram::DiagramN(d) => d
+                .map(|s| self.rename_diagram(s), |r| self.rename_rewrite(r))
+                .into(),
+        }
+    }
+
+    fn rename_rewrite(&self, rewrite: &Rewrite) -> Rewrite {
+        let rename = |d: Diagram0| Diagram0::new(self.rename(d.generator), d.orientation);
+        match rewrite {
+            Rewrite::Rewrite0(r) => match (r.source(), r.target()) {
+                (Some(source), Some(target)) => {
+                    Rewrite0::new(rename(source), rename(target), r.label().cloned()).into()
+                }
+                _ => r.clone().into(),
+            },
+            Rewrite::RewriteN(r) => RewriteN::new(
+                r.dimension(),
+                r.cones()
+                    .iter()
+                    .map(|cone: &Cone| cone.map(|r| self.rename_rewrite(r)))
+                    .collect(),
+            )
+            .into(),
+        }
+    }
+}
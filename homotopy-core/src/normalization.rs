@@ -0,0 +1,133 @@
+use std::convert::TryInto;
+
+use crate::{
+    common::Height,
+    rewrite::{Cone, Cospan},
+    Diagram, DiagramN, Rewrite, RewriteN,
+};
+
+impl DiagramN {
+    /// Removes every redundant level of the diagram, in every dimension.
+    ///
+    /// Returns the normal form together with a degeneracy rewrite from the normal form into the
+    /// original diagram, which witnesses that the original is a padded copy of the normal form.
+    ///
+    /// Only singular content is normalised: the regular slices (and so the boundary) of the
+    /// diagram are left untouched. A level in a singular slice is only removed if none of the
+    /// rewrites into that slice need it.
+    pub fn normalize(&self) -> (Self, RewriteN) {
+        normalize_relative_n(self, &[]).0
+    }
+
+    /// Checks whether the diagram is already in normal form.
+    pub fn is_normal(&self) -> bool {
+        self.normalize().1.is_identity()
+    }
+}
+
+/// Normalises a diagram relative to a collection of sinks, i.e. rewrites into the diagram that
+/// must factor through the resulting degeneracy.
+///
+/// Returns the normal form, the degeneracy from the normal form into the diagram, and the
+/// factorisation of each sink through the degeneracy.
+fn normalize_relative(diagram: &Diagram, sinks: &[Rewrite]) -> (Diagram, Rewrite, Vec<Rewrite>) {
+    match diagram {
+        Diagram::Diagram0(_) => (diagram.clone(), Rewrite::identity(0), sinks.to_vec()),
+        Diagram::DiagramN(diagram) => {
+            let sinks: Vec<RewriteN> = sinks
+                .iter()
+                .map(|sink| sink.clone().try_into().unwrap())
+                .collect();
+            let ((normal, degeneracy), factorized) = normalize_relative_n(diagram, &sinks);
+            (
+                normal.into(),
+                degeneracy.into(),
+                factorized.into_iter().map(Into::into).collect(),
+            )
+        }
+    }
+}
+
+fn normalize_relative_n(
+    diagram: &DiagramN,
+    sinks: &[RewriteN],
+) -> ((DiagramN, RewriteN), Vec<RewriteN>) {
+    let dimension = diagram.dimension();
+
+    let mut cospans: Vec<Cospan> = vec![];
+    let mut degeneracy_cones: Vec<Cone> = vec![];
+    let mut sink_cones: Vec<Vec<Cone>> = vec![vec![]; sinks.len()];
+
+    for (height, cospan) in diagram.cospans().iter().enumerate() {
+        let cones: Vec<Option<&Cone>> = sinks
+            .iter()
+            .map(|sink| sink.cone_over_target(height))
+            .collect();
+
+        // A sink which is the identity at this height pins the whole singular slice.
+        if cones.iter().any(Option::is_none) {
+            for (sink_cones, cone) in sink_cones.iter_mut().zip(cones) {
+                sink_cones.extend(cone.cloned());
+            }
+            cospans.push(cospan.clone());
+            continue;
+        }
+
+        let cones: Vec<&Cone> = cones.into_iter().flatten().collect();
+
+        // Normalise the singular slice relative to everything that maps into it.
+        let slice = diagram.slice(Height::Singular(height)).unwrap();
+        let mut slice_sinks = vec![cospan.forward.clone(), cospan.backward.clone()];
+        for cone in &cones {
+            slice_sinks.extend(cone.regular_slices().iter().cloned());
+            slice_sinks.extend(cone.singular_slices().iter().cloned());
+        }
+        let (_, slice_degeneracy, factorized) = normalize_relative(&slice, &slice_sinks);
+        let mut factorized = factorized.into_iter();
+
+        let normal_cospan = Cospan {
+            forward: factorized.next().unwrap(),
+            backward: factorized.next().unwrap(),
+        };
+
+        if normal_cospan.is_redundant() && cones.iter().all(|cone| cone.is_unit()) {
+            // Remove the level.
+            degeneracy_cones.push(Cone::new_unit(
+                cospans.len(),
+                cospan.clone(),
+                cospan.forward.clone(),
+            ));
+            continue;
+        }
+
+        for (sink_cones, cone) in sink_cones.iter_mut().zip(cones) {
+            let regular_slices = factorized.by_ref().take(cone.len() + 1).collect();
+            let singular_slices = factorized.by_ref().take(cone.len()).collect();
+            sink_cones.push(Cone::new(
+                cone.index,
+                cone.source().to_vec(),
+                normal_cospan.clone(),
+                regular_slices,
+                singular_slices,
+            ));
+        }
+
+        degeneracy_cones.push(Cone::new(
+            cospans.len(),
+            vec![normal_cospan.clone()],
+            cospan.clone(),
+            vec![cospan.forward.clone(), cospan.backward.clone()],
+            vec![slice_degeneracy],
+        ));
+        cospans.push(normal_cospan);
+    }
+
+    let normal = DiagramN::new(diagram.source(), cospans);
+    let degeneracy = RewriteN::new(dimension, degeneracy_cones);
+    let factorized = sink_cones
+        .into_iter()
+        .map(|cones| RewriteN::new(dimension, cones))
+        .collect();
+
+    ((normal, degeneracy), factorized)
+}
@@ -36,6 +36,17 @@ pub enum Homotopy {
     FullBraid,
 }
 
+/// Two strands passing each other at a braid in the 2-dimensional projection of a diagram.
+///
+/// Each strand is given by the coordinates of the points on it just below and just above the
+/// braid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Crossing<const N: usize> {
+    pub coord: Coordinate<N>,
+    pub over: [Coordinate<N>; 2],
+    pub under: [Coordinate<N>; 2],
+}
+
 /// Diagram analysis that determines the generator displayed at any point in the 2-dimensional
 /// projection of a diagram, as well as information about homotopies.
 #[derive(Clone, Debug)]
@@ -43,6 +54,7 @@ pub struct Projection<const N: usize> {
     generators: IdxVec<NodeIndex, Diagram0>,
     front_generators: IdxVec<NodeIndex, (Diagram0, bool)>,
     homotopies: IdxVec<NodeIndex, Option<Homotopy>>,
+    crossings: Vec<Crossing<N>>,
     coord_to_node: FastHashMap<Coordinate<N>, NodeIndex>,
 }
 
@@ -80,6 +92,7 @@ impl<const N: usize> Projection<N> {
         let mut generators = IdxVec::with_capacity(graph.node_count());
         let mut homotopies = IdxVec::with_capacity(graph.node_count());
         let mut front_generators = IdxVec::with_capacity(graph.node_count());
+        let mut crossings = vec![];
         let mut coord_to_node =
             FastHashMap::with_capacity_and_hasher(graph.node_count(), FastHasher::default());
 
@@ -216,9 +229,9 @@ impl<const N: usize> Projection<N> {
                     // To determine which, we check if the incoming strand crosses over the outgoing strand using the layout.
                     let &i = front_inputs.first().unwrap();
                     let &j = front_outputs.first().unwrap();
-                    let position_node = layout[&coord][0];
-                    let position_input_wire = layout[&input_coords[i]][0];
-                    let position_output_wire = layout[&output_coords[j]][0];
+                    let position_node = layout.get(&coord)?[0];
+                    let position_input_wire = layout.get(&input_coords[i])?[0];
+                    let position_output_wire = layout.get(&output_coords[j])?[0];
 
                     if matches!(
                         (
@@ -228,6 +241,13 @@ impl<const N: usize> Projection<N> {
                         (Some(Ordering::Less), Some(Ordering::Greater))
                             | (Some(Ordering::Greater), Some(Ordering::Less))
                     ) {
+                        if inputs == 2 && outputs == 2 {
+                            crossings.push(Crossing {
+                                coord,
+                                over: [input_coords[i], output_coords[j]],
+                                under: [input_coords[1 - i], output_coords[1 - j]],
+                            });
+                        }
                         Some(Homotopy::FullBraid)
                     } else {
                         Some(Homotopy::HalfBraid)
@@ -245,6 +265,7 @@ impl<const N: usize> Projection<N> {
             generators,
             front_generators,
             homotopies,
+            crossings,
             coord_to_node,
         })
     }
@@ -260,6 +281,11 @@ impl<const N: usize> Projection<N> {
     pub fn homotopy(&self, p: Coordinate<N>) -> Option<Homotopy> {
         self.homotopies[self.coord_to_node[&p]]
     }
+
+    /// The braids in the projection between exactly two strands.
+    pub fn crossings(&self) -> &[Crossing<N>] {
+        &self.crossings
+    }
 }
 
 /// Diagram analysis that finds the depth of cells in the 2-dimensional projection of a diagram.
@@ -1,5 +1,4 @@
 use std::{
-    cell::RefCell,
     cmp::Ordering,
     convert::{From, Into},
     fmt,
@@ -7,9 +6,9 @@ use std::{
     ops::Range,
 };
 
-use hashconsing::{HConsed, HConsign, HashConsign};
+use hashconsing::HConsed;
 use homotopy_common::hash::{FastHashMap, FastHashSet};
-use once_cell::unsync::OnceCell;
+use once_cell::sync::OnceCell;
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use thiserror::Error;
 
@@ -18,18 +17,11 @@ use crate::{
         BoundaryPath, DimensionError, Generator, Label, LabelIdentifications, Mode, Orientation,
         RegularHeight, SingularHeight,
     },
-    diagram::Diagram,
+    diagram::{Diagram, DiagramN},
+    session::{self, SessionId},
     Boundary, Diagram0, Height,
 };
 
-thread_local! {
-    static REWRITE_FACTORY: RefCell<HConsign<RewriteInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
-
-    static CONE_FACTORY: RefCell<HConsign<ConeInternal>> =
-        RefCell::new(HConsign::with_capacity(37));
-}
-
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct Cospan {
     pub forward: Rewrite,
@@ -228,6 +220,18 @@ impl Rewrite {
         }
     }
 
+    /// Reverse the rewrite in dimension `k`, as in [`Diagram::reverse_in`], given its source and
+    /// target diagrams.
+    #[must_use]
+    pub fn reverse_in(&self, k: usize, source: &Diagram, target: &Diagram) -> Self {
+        match (self, source, target) {
+            (Self::RewriteN(r), Diagram::DiagramN(source), Diagram::DiagramN(target)) => {
+                r.reverse_in(k, source, target).into()
+            }
+            _ => self.clone(),
+        }
+    }
+
     #[must_use]
     pub fn orientation_transform(&self, k: Orientation) -> Self {
         self.orientation_transform_above(k, self.dimension())
@@ -457,9 +461,21 @@ impl Rewrite0 {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq)]
 pub struct RewriteN(HConsed<RewriteInternal>);
 
+impl PartialEq for RewriteN {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.session == other.0.session && self.0 == other.0
+    }
+}
+
+impl Hash for RewriteN {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
 impl Serialize for RewriteN {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -474,8 +490,7 @@ impl<'de> Deserialize<'de> for RewriteN {
     where
         D: serde::Deserializer<'de>,
     {
-        Deserialize::deserialize(deserializer)
-            .map(|r| RewriteN(REWRITE_FACTORY.with(|factory| factory.borrow_mut().mk(r))))
+        Deserialize::deserialize(deserializer).map(|r| RewriteN(session::rewrite(r)))
     }
 }
 
@@ -497,13 +512,12 @@ impl RewriteN {
         // cones.
         cones.retain(|cone| !cone.is_identity());
 
-        Self(REWRITE_FACTORY.with(|factory| {
-            factory.borrow_mut().mk(RewriteInternal {
-                dimension,
-                cones,
-                max_generator_source: OnceCell::new(),
-                max_generator_target: OnceCell::new(),
-            })
+        Self(session::rewrite(RewriteInternal {
+            dimension,
+            cones,
+            max_generator_source: OnceCell::new(),
+            max_generator_target: OnceCell::new(),
+            session: SessionId::default(),
         }))
     }
 
@@ -576,8 +590,79 @@ impl RewriteN {
         Self::new(self.dimension(), cones)
     }
 
-    pub(crate) fn collect_garbage() {
-        REWRITE_FACTORY.with(|factory| factory.borrow_mut().collect_to_fit());
+    /// See [`Rewrite::reverse_in`].
+    #[must_use]
+    pub fn reverse_in(&self, k: usize, source: &DiagramN, target: &DiagramN) -> Self {
+        if k == 0 || k > self.dimension() {
+            return self.clone();
+        }
+
+        if k == self.dimension() {
+            let cones = self
+                .cones()
+                .iter()
+                .rev()
+                .map(|cone| {
+                    Cone::new(
+                        source.size() - cone.index - cone.len(),
+                        cone.source()
+                            .iter()
+                            .rev()
+                            .cloned()
+                            .map(Cospan::flip)
+                            .collect(),
+                        cone.target().clone().flip(),
+                        cone.regular_slices().iter().rev().cloned().collect(),
+                        cone.singular_slices().iter().rev().cloned().collect(),
+                    )
+                })
+                .collect();
+            return Self::new(self.dimension(), cones);
+        }
+
+        let source_slices: Vec<Diagram> = source.slices().collect();
+        let target_slices: Vec<Diagram> = target.slices().collect();
+        let cones = self
+            .cones()
+            .iter()
+            .zip(self.targets())
+            .map(|(cone, height)| {
+                let source = |i: usize| &source_slices[cone.index * 2 + i];
+                let target = |i: usize| &target_slices[height * 2 + i];
+                Cone::new(
+                    cone.index,
+                    cone.source()
+                        .iter()
+                        .enumerate()
+                        .map(|(i, cospan)| Cospan {
+                            forward: cospan
+                                .forward
+                                .reverse_in(k, source(2 * i), source(2 * i + 1)),
+                            backward: cospan.backward.reverse_in(
+                                k,
+                                source(2 * i + 2),
+                                source(2 * i + 1),
+                            ),
+                        })
+                        .collect(),
+                    Cospan {
+                        forward: cone.target().forward.reverse_in(k, target(0), target(1)),
+                        backward: cone.target().backward.reverse_in(k, target(2), target(1)),
+                    },
+                    cone.regular_slices()
+                        .iter()
+                        .enumerate()
+                        .map(|(i, slice)| slice.reverse_in(k, source(2 * i), target(1)))
+                        .collect(),
+                    cone.singular_slices()
+                        .iter()
+                        .enumerate()
+                        .map(|(i, slice)| slice.reverse_in(k, source(2 * i + 1), target(1)))
+                        .collect(),
+                )
+            })
+            .collect();
+        Self::new(self.dimension(), cones)
     }
 
     pub(crate) fn cones(&self) -> &[Cone] {
@@ -878,13 +963,15 @@ impl RewriteN {
 }
 
 #[derive(Clone, Eq, Serialize, Deserialize)]
-struct RewriteInternal {
+pub(crate) struct RewriteInternal {
     dimension: usize,
     cones: Vec<Cone>,
     #[serde(skip)]
     max_generator_source: OnceCell<Option<Diagram0>>,
     #[serde(skip)]
     max_generator_target: OnceCell<Option<Diagram0>>,
+    #[serde(skip)]
+    session: SessionId,
 }
 
 impl PartialEq for RewriteInternal {
@@ -1010,20 +1097,37 @@ impl<'a> TryFrom<&'a mut Rewrite> for &'a mut RewriteN {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub(crate) struct ConeInternal {
     source: Vec<Cospan>,
     target: Cospan,
     regular_slices: Vec<Rewrite>,
     singular_slices: Vec<Rewrite>,
+    #[serde(skip)]
+    session: SessionId,
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq)]
 pub struct Cone {
     pub(crate) index: usize,
     pub(crate) internal: HConsed<ConeInternal>,
 }
 
+impl PartialEq for Cone {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+            && self.internal.session == other.internal.session
+            && self.internal == other.internal
+    }
+}
+
+impl Hash for Cone {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.internal.hash(state);
+    }
+}
+
 impl Serialize for Cone {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -1048,7 +1152,7 @@ impl<'de> Deserialize<'de> for Cone {
         }
         Deserialize::deserialize(deserializer).map(|c: ConeUnshared| Cone {
             index: c.index,
-            internal: CONE_FACTORY.with(|factory| factory.borrow_mut().mk(c.internal)),
+            internal: session::cone(c.internal),
         })
     }
 }
@@ -1066,13 +1170,12 @@ impl Cone {
         assert_eq!(regular_slices.len(), singular_slices.len() + 1);
         Self {
             index,
-            internal: CONE_FACTORY.with(|factory| {
-                factory.borrow_mut().mk(ConeInternal {
-                    source,
-                    target,
-                    regular_slices,
-                    singular_slices,
-                })
+            internal: session::cone(ConeInternal {
+                source,
+                target,
+                regular_slices,
+                singular_slices,
+                session: SessionId::default(),
             }),
         }
     }
@@ -1121,11 +1224,6 @@ impl Cone {
         }
     }
 
-    pub(crate) fn collect_garbage() {
-        CONE_FACTORY.with(|factory| factory.borrow_mut().collect_to_fit());
-    }
-
-    #[allow(dead_code)]
     pub(crate) fn is_unit(&self) -> bool {
         self.source().is_empty()
     }
@@ -1179,6 +1277,19 @@ impl Cone {
     }
 }
 
+// the session is left out, so that debug output agrees between sessions
+#[allow(clippy::missing_fields_in_debug)]
+impl fmt::Debug for ConeInternal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConeInternal")
+            .field("source", &self.source)
+            .field("target", &self.target)
+            .field("regular_slices", &self.regular_slices)
+            .field("singular_slices", &self.singular_slices)
+            .finish()
+    }
+}
+
 impl fmt::Debug for Cone {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Cone")
@@ -0,0 +1,331 @@
+use std::time::Duration;
+
+use homotopy_common::hash::FastHashMap;
+use instant::Instant;
+use thiserror::Error;
+
+use crate::{
+    common::{Boundary, Direction, Height, SingularHeight},
+    contraction::{contract_in_path, Bias, ContractExpand},
+    expansion::expand_in_path,
+    signature::Signature,
+    typecheck::typecheck_cospan,
+    Cospan, Diagram, DiagramN, Rewrite,
+};
+
+/// A single contraction or expansion of a diagram.
+///
+/// The location is the interior path to the slice of the diagram in which the move takes place,
+/// in the same way as the homotopy actions of the proof assistant.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Move {
+    Contract {
+        location: Vec<Height>,
+        height: SingularHeight,
+        direction: Direction,
+        bias: Option<Bias>,
+    },
+    Expand {
+        location: Vec<Height>,
+        point: [Height; 2],
+        direction: Direction,
+    },
+}
+
+impl Move {
+    /// Apply the move to a diagram, returning `None` if the move is invalid or ill-typed.
+    pub fn apply(&self, diagram: &DiagramN, signature: &impl Signature) -> Option<DiagramN> {
+        self.homotopy(diagram, signature)?.target().try_into().ok()
+    }
+
+    /// The homotopy performing the move on a diagram, from the diagram to the result of the move,
+    /// or `None` if the move is invalid or ill-typed.
+    pub fn homotopy(&self, diagram: &DiagramN, signature: &impl Signature) -> Option<DiagramN> {
+        let homotopy = match self {
+            Self::Contract {
+                location,
+                height,
+                direction,
+                bias,
+            } => diagram
+                .clone()
+                .identity()
+                .contract(
+                    Boundary::Target.into(),
+                    &mut location.clone(),
+                    *height,
+                    *direction,
+                    *bias,
+                    signature,
+                )
+                .ok()?,
+            Self::Expand {
+                location,
+                point,
+                direction,
+            } => diagram
+                .clone()
+                .identity()
+                .expand(
+                    Boundary::Target.into(),
+                    &mut location.clone(),
+                    *point,
+                    *direction,
+                    signature,
+                )
+                .ok()?,
+        };
+
+        Some(homotopy)
+    }
+}
+
+/// Limits on how far a search may go before giving up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget {
+    /// Maximum number of moves in a homotopy.
+    pub depth: usize,
+    /// Maximum number of distinct diagrams to visit.
+    pub nodes: usize,
+    /// Maximum amount of time to spend searching.
+    pub time: Option<Duration>,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            depth: 4,
+            nodes: 10_000,
+            time: None,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error("diagrams do not have the same boundaries")]
+    Boundary,
+
+    #[error("no homotopy found within the search depth")]
+    NotFound,
+
+    #[error("search exceeded the node budget")]
+    NodeBudget,
+
+    #[error("search exceeded the time budget")]
+    TimeBudget,
+}
+
+/// Search for a sequence of moves which turns `source` into `target`.
+///
+/// The search is breadth-first, so the homotopy found is one of minimal length. Diagrams are
+/// deduplicated by their hash-consed identity.
+pub fn search(
+    source: &DiagramN,
+    target: &DiagramN,
+    signature: &impl Signature,
+    budget: &Budget,
+) -> Result<Vec<Move>, SearchError> {
+    if source.dimension() != target.dimension()
+        || source.source() != target.source()
+        || source.target() != target.target()
+    {
+        return Err(SearchError::Boundary);
+    }
+
+    let start = Instant::now();
+    let mut parents: FastHashMap<DiagramN, Option<(DiagramN, Move)>> = FastHashMap::default();
+    parents.insert(source.clone(), None);
+
+    if source == target {
+        return Ok(vec![]);
+    }
+
+    let mut frontier = vec![source.clone()];
+
+    for _ in 0..budget.depth {
+        let mut next = vec![];
+
+        for diagram in &frontier {
+            for location in locations(diagram, &[]) {
+                if budget.time.is_some_and(|time| start.elapsed() > time) {
+                    return Err(SearchError::TimeBudget);
+                }
+
+                for (m, result) in moves(diagram, &location, signature) {
+                    if parents.contains_key(&result) {
+                        continue;
+                    }
+
+                    parents.insert(result.clone(), Some((diagram.clone(), m)));
+
+                    if result == *target {
+                        return Ok(trace(&parents, target));
+                    }
+
+                    if parents.len() >= budget.nodes {
+                        return Err(SearchError::NodeBudget);
+                    }
+
+                    next.push(result);
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+
+        frontier = next;
+    }
+
+    Err(SearchError::NotFound)
+}
+
+fn trace(
+    parents: &FastHashMap<DiagramN, Option<(DiagramN, Move)>>,
+    target: &DiagramN,
+) -> Vec<Move> {
+    let mut moves = vec![];
+    let mut diagram = target;
+
+    while let Some((parent, m)) = &parents[diagram] {
+        moves.push(m.clone());
+        diagram = parent;
+    }
+
+    moves.reverse();
+    moves
+}
+
+/// All valid moves in the slice of a diagram at the given location, together with the diagrams
+/// they result in.
+///
+/// This covers contractions at every height, in both directions and with every bias, as well as
+/// expansions at every point and in both directions. Ill-typed moves are dropped.
+pub fn moves(
+    diagram: &DiagramN,
+    location: &[Height],
+    signature: &impl Signature,
+) -> Vec<(Move, DiagramN)> {
+    use Direction::{Backward, Forward};
+
+    let mut moves = vec![];
+
+    let mut slice = diagram.clone();
+    for height in location {
+        match slice.slice(*height) {
+            Some(Diagram::DiagramN(s)) => slice = s,
+            _ => return moves,
+        }
+    }
+
+    for height in 0..slice.size() {
+        for direction in [Forward, Backward] {
+            for bias in [
+                None,
+                Some(Bias::Higher),
+                Some(Bias::Same),
+                Some(Bias::Lower),
+            ] {
+                let Ok(ContractExpand { contract, expand }) = contract_in_path(
+                    diagram,
+                    &mut location.to_vec(),
+                    height,
+                    direction,
+                    bias,
+                    true,
+                ) else {
+                    continue;
+                };
+
+                let cospan = Cospan {
+                    forward: contract.clone().into(),
+                    backward: expand.clone().into(),
+                };
+                if typecheck_cospan(diagram.clone().into(), cospan, signature).is_err() {
+                    continue;
+                }
+
+                let Ok(result) = diagram
+                    .clone()
+                    .rewrite_forward(&contract)
+                    .and_then(|d| d.rewrite_backward(&expand))
+                else {
+                    continue;
+                };
+
+                moves.push((
+                    Move::Contract {
+                        location: location.to_vec(),
+                        height,
+                        direction,
+                        bias,
+                    },
+                    result,
+                ));
+            }
+        }
+    }
+
+    // Expansions can only be propagated through singular slices.
+    if slice.dimension() < 2 || location.iter().any(|h| matches!(h, Height::Regular(_))) {
+        return moves;
+    }
+
+    for h0 in Height::for_size(slice.size()) {
+        let Some(Diagram::DiagramN(subslice)) = slice.slice(h0) else {
+            continue;
+        };
+        for h1 in Height::for_size(subslice.size()) {
+            if matches!(h0, Height::Regular(_)) != matches!(h1, Height::Regular(_)) {
+                continue;
+            }
+            for direction in [Forward, Backward] {
+                let Ok(expand) =
+                    expand_in_path(diagram, &mut location.to_vec(), [h0, h1], direction)
+                else {
+                    continue;
+                };
+
+                let cospan = Cospan {
+                    forward: Rewrite::identity(diagram.dimension()),
+                    backward: expand.clone().into(),
+                };
+                if typecheck_cospan(diagram.clone().into(), cospan, signature).is_err() {
+                    continue;
+                }
+
+                let Ok(result) = diagram.clone().rewrite_backward(&expand) else {
+                    continue;
+                };
+
+                moves.push((
+                    Move::Expand {
+                        location: location.to_vec(),
+                        point: [h0, h1],
+                        direction,
+                    },
+                    result,
+                ));
+            }
+        }
+    }
+
+    moves
+}
+
+/// The locations of all slices of a diagram in which moves can take place.
+fn locations(diagram: &DiagramN, location: &[Height]) -> Vec<Vec<Height>> {
+    let mut result = vec![location.to_vec()];
+
+    for height in Height::for_size(diagram.size()) {
+        if let Some(Diagram::DiagramN(slice)) = diagram.slice(height) {
+            let mut location = location.to_vec();
+            location.push(height);
+            result.extend(locations(&slice, &location));
+        }
+    }
+
+    result
+}
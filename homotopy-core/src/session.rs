@@ -0,0 +1,285 @@
+//! The hash-consing tables and caches behind diagrams, rewrites and labels.
+//!
+//! [`DiagramN`], [`RewriteN`], [`Cone`] and [`Label`] are hash-consed, so that equal values share
+//! their memory and compare in constant time. The tables that make this work, along with the
+//! caches used by typechecking, are kept per thread: a session is the state of one thread, and
+//! each thread has exactly one current session. There is no handle to switch between sessions, so
+//! independent proofs run in separate sessions by running on separate threads, or one after the
+//! other on the same thread with a [`reset`] in between.
+//!
+//! Each value records its session, and values from different sessions never compare equal, even
+//! when they have the same structure. Values should move between sessions serialized.
+//!
+//! The entries of dropped values linger in the tables until they are swept by [`collect_garbage`],
+//! or until a table reaches its cap. The sizes of the tables and caches are reported by
+//! [`statistics`] and can be capped with [`set_limits`].
+//!
+//! [`DiagramN`]: crate::DiagramN
+//! [`RewriteN`]: crate::RewriteN
+//! [`Cone`]: crate::rewrite::Cone
+//! [`Label`]: crate::common::Label
+use std::{
+    cell::{Cell, RefCell},
+    hash::Hash,
+    sync::atomic::{self, AtomicUsize},
+};
+
+use hashconsing::{HConsed, HConsign, HashConsign};
+use homotopy_common::hash::FastHashMap;
+
+use crate::{
+    common::LabelInternal,
+    diagram::DiagramInternal,
+    rewrite::{ConeInternal, RewriteInternal},
+    typecheck::Embedding,
+    Rewrite,
+};
+
+thread_local! {
+    static SESSION: Session = Session::new();
+}
+
+static SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The session which a hash-consed value belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct SessionId(usize);
+
+impl Default for SessionId {
+    /// The session of the current thread.
+    fn default() -> Self {
+        SESSION.with(|session| session.id.get())
+    }
+}
+
+impl SessionId {
+    fn fresh() -> Self {
+        Self(SESSIONS.fetch_add(1, atomic::Ordering::Relaxed))
+    }
+}
+
+/// Caps on the sizes of the tables and caches of a session.
+///
+/// A table which reaches its cap is swept before it grows any further, but entries of values which
+/// are still alive are never dropped. When the values in use fill more than half of the cap, the
+/// table is instead swept once it has doubled in size since the last sweep, so it can exceed its
+/// cap. A cache which reaches its cap is cleared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub table_size: Option<usize>,
+    pub cache_size: Option<usize>,
+}
+
+/// The number of entries in each table and cache of a session, including the entries of dropped
+/// values which have not been swept yet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+    pub diagrams: usize,
+    pub rewrites: usize,
+    pub cones: usize,
+    pub labels: usize,
+    pub restrictions: usize,
+}
+
+struct Session {
+    id: Cell<SessionId>,
+    diagrams: Table<DiagramInternal>,
+    rewrites: Table<RewriteInternal>,
+    cones: Table<ConeInternal>,
+    labels: Table<LabelInternal>,
+    restrictions: RefCell<FastHashMap<(Rewrite, Embedding), Rewrite>>,
+    limits: Cell<Limits>,
+}
+
+impl Session {
+    fn new() -> Self {
+        Self {
+            id: Cell::new(SessionId::fresh()),
+            diagrams: Table::default(),
+            rewrites: Table::default(),
+            cones: Table::default(),
+            labels: Table::default(),
+            restrictions: RefCell::default(),
+            limits: Cell::default(),
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics {
+            diagrams: self.diagrams.len(),
+            rewrites: self.rewrites.len(),
+            cones: self.cones.len(),
+            labels: self.labels.len(),
+            restrictions: self.restrictions.borrow().len(),
+        }
+    }
+
+    /// Start over under a fresh id with empty tables and caches, keeping the limits.
+    fn reset(&self) {
+        self.id.set(SessionId::fresh());
+        self.diagrams.clear();
+        self.rewrites.clear();
+        self.cones.clear();
+        self.labels.clear();
+        self.restrictions.borrow_mut().clear();
+    }
+
+    /// Clear the cache of restrictions if it has reached its cap.
+    fn fit_restrictions(&self) {
+        let mut restrictions = self.restrictions.borrow_mut();
+        if self
+            .limits
+            .get()
+            .cache_size
+            .is_some_and(|limit| restrictions.len() >= limit)
+        {
+            restrictions.clear();
+        }
+    }
+}
+
+struct Table<T: Hash + Eq + Clone> {
+    table: RefCell<HConsign<T>>,
+    /// Twice the size of the table after its last sweep.
+    mark: Cell<usize>,
+}
+
+impl<T: Hash + Eq + Clone> Default for Table<T> {
+    fn default() -> Self {
+        Self {
+            table: RefCell::new(HConsign::with_capacity(37)),
+            mark: Cell::default(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Table<T> {
+    fn mk(&self, value: T, limit: Option<usize>) -> HConsed<T> {
+        self.fit(limit);
+        self.table.borrow_mut().mk(value)
+    }
+
+    /// Sweep the table if it has reached its cap, and has doubled in size since the last sweep.
+    ///
+    /// Waiting for the table to double keeps the cost of sweeping linear in the number of entries
+    /// made, even when more values are in use than the cap allows.
+    fn fit(&self, limit: Option<usize>) {
+        let mut table = self.table.borrow_mut();
+        if limit.is_some_and(|limit| table.len() >= limit.max(self.mark.get())) {
+            table.collect_to_fit();
+            self.mark.set(2 * table.len());
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.table.borrow().len()
+    }
+
+    fn collect_garbage(&self) {
+        let mut table = self.table.borrow_mut();
+        table.collect_to_fit();
+        self.mark.set(2 * table.len());
+    }
+
+    fn clear(&self) {
+        *self.table.borrow_mut() = HConsign::with_capacity(37);
+        self.mark.set(0);
+    }
+}
+
+pub(crate) fn diagram(diagram: DiagramInternal) -> HConsed<DiagramInternal> {
+    SESSION.with(|session| {
+        session
+            .diagrams
+            .mk(diagram, session.limits.get().table_size)
+    })
+}
+
+pub(crate) fn rewrite(rewrite: RewriteInternal) -> HConsed<RewriteInternal> {
+    SESSION.with(|session| {
+        session
+            .rewrites
+            .mk(rewrite, session.limits.get().table_size)
+    })
+}
+
+pub(crate) fn cone(cone: ConeInternal) -> HConsed<ConeInternal> {
+    SESSION.with(|session| session.cones.mk(cone, session.limits.get().table_size))
+}
+
+pub(crate) fn label(label: LabelInternal) -> HConsed<LabelInternal> {
+    SESSION.with(|session| session.labels.mk(label, session.limits.get().table_size))
+}
+
+/// The restriction of a rewrite to an embedding, if it has been cached.
+pub(crate) fn restriction(rewrite: &Rewrite, embedding: &Embedding) -> Option<Rewrite> {
+    SESSION.with(|session| {
+        session
+            .restrictions
+            .borrow()
+            .get(&(rewrite.clone(), embedding.clone()))
+            .cloned()
+    })
+}
+
+pub(crate) fn cache_restriction(rewrite: Rewrite, embedding: Embedding, restricted: Rewrite) {
+    SESSION.with(|session| {
+        session.fit_restrictions();
+        session
+            .restrictions
+            .borrow_mut()
+            .insert((rewrite, embedding), restricted);
+    });
+}
+
+pub(crate) fn clear_restrictions() {
+    SESSION.with(|session| session.restrictions.borrow_mut().clear());
+}
+
+/// Sweep the entries of dropped values from the tables of the current session.
+///
+/// Tables own a copy of each of their values, which keeps the values they refer to alive, so the
+/// tables are swept until nothing more is freed.
+pub fn collect_garbage() {
+    SESSION.with(|session| loop {
+        let before = session.statistics();
+        session.diagrams.collect_garbage();
+        session.rewrites.collect_garbage();
+        session.cones.collect_garbage();
+        session.labels.collect_garbage();
+        if session.statistics() == before {
+            break;
+        }
+    });
+}
+
+/// Replace the session of the current thread with a new one, with the same limits.
+///
+/// The tables and caches of the old session are dropped. Values made before the reset remain
+/// usable, but they belong to the old session, so they never compare equal to values made after it.
+pub fn reset() {
+    SESSION.with(Session::reset);
+}
+
+/// The sizes of the tables and caches of the current session.
+pub fn statistics() -> Statistics {
+    SESSION.with(Session::statistics)
+}
+
+pub fn limits() -> Limits {
+    SESSION.with(|session| session.limits.get())
+}
+
+/// Cap the tables and caches of the current session.
+///
+/// Tables and caches which have already reached their new caps are swept or cleared straight away.
+pub fn set_limits(limits: Limits) {
+    SESSION.with(|session| {
+        session.limits.set(limits);
+        session.diagrams.fit(limits.table_size);
+        session.rewrites.fit(limits.table_size);
+        session.cones.fit(limits.table_size);
+        session.labels.fit(limits.table_size);
+        session.fit_restrictions();
+    });
+}
@@ -1,5 +1,11 @@
+use homotopy_common::hash::{FastHashMap, FastHashSet};
+use thiserror::Error;
+
 use crate::{
+    check::MalformedDiagram,
+    common::{Boundary, Mode},
     diagram::{globularity, NewDiagramError},
+    typecheck::{typecheck, TypeError},
     Diagram, Diagram0, DiagramN, Generator,
 };
 
@@ -27,14 +33,190 @@ pub trait Signature {
             })
             .collect()
     }
+
+    /// Check that every generator of the signature is well-formed, returning a report for each
+    /// generator in the order of [`Signature::generators`].
+    fn validate(&self) -> Vec<GeneratorReport>
+    where
+        Self: Sized,
+    {
+        validate(self)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ValidationError {
+    #[error("the generator has no diagram in the signature")]
+    Missing,
+
+    #[error("the id of the generator is shared with another generator")]
+    DuplicateId,
+
+    #[error("the generator has dimension {0} but its diagram has dimension {1}")]
+    Dimension(usize, usize),
+
+    #[error("the diagram of the generator is malformed: {0:?}")]
+    Malformed(Vec<MalformedDiagram>),
+
+    #[error("the diagram of the generator contains {0:?}, which is not in the signature")]
+    Unknown(Generator),
+
+    #[error("the {0:?} boundary of the generator is ill-typed: {1}")]
+    IllTyped(Boundary, TypeError),
+
+    #[error("the generator depends on itself through its boundaries")]
+    SelfDependent,
+}
+
+/// The problems found with a single generator by [`Signature::validate`].
+#[derive(Debug)]
+pub struct GeneratorReport {
+    pub generator: Generator,
+    pub errors: Vec<ValidationError>,
+}
+
+impl GeneratorReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+fn validate(signature: &impl Signature) -> Vec<GeneratorReport> {
+    let generators: Vec<_> = signature.generators().collect();
+
+    let mut ids: FastHashMap<usize, usize> = FastHashMap::default();
+    for generator in &generators {
+        *ids.entry(generator.id).or_default() += 1;
+    }
+
+    // The generators appearing in the boundaries of each generator.
+    let dependencies: FastHashMap<Generator, Vec<Generator>> = generators
+        .iter()
+        .filter_map(|&generator| {
+            let Diagram::DiagramN(diagram) = signature.generator_info(generator)?.diagram() else {
+                return None;
+            };
+            let mut boundaries: FastHashSet<_> =
+                diagram.source().generators().into_keys().collect();
+            boundaries.extend(diagram.target().generators().into_keys());
+            Some((generator, boundaries.into_iter().collect()))
+        })
+        .collect();
+
+    generators
+        .iter()
+        .map(|&generator| {
+            let mut errors = vec![];
+            if ids[&generator.id] > 1 {
+                errors.push(ValidationError::DuplicateId);
+            }
+            match signature.generator_info(generator) {
+                None => errors.push(ValidationError::Missing),
+                Some(info) => validate_diagram(signature, generator, info.diagram(), &mut errors),
+            }
+            if depends_on_itself(&dependencies, generator) {
+                errors.push(ValidationError::SelfDependent);
+            }
+            GeneratorReport { generator, errors }
+        })
+        .collect()
+}
+
+fn validate_diagram(
+    signature: &impl Signature,
+    generator: Generator,
+    diagram: &Diagram,
+    errors: &mut Vec<ValidationError>,
+) {
+    if diagram.dimension() != generator.dimension {
+        errors.push(ValidationError::Dimension(
+            generator.dimension,
+            diagram.dimension(),
+        ));
+        return;
+    }
+
+    if let Err(malformed) = diagram.check(Mode::Deep) {
+        errors.push(ValidationError::Malformed(malformed));
+        return;
+    }
+
+    let mut unknown: Vec<_> = diagram
+        .generators()
+        .into_keys()
+        .filter(|&g| g != generator && signature.generator_info(g).is_none())
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort_by_key(|g| (g.dimension, g.id));
+        errors.extend(unknown.into_iter().map(ValidationError::Unknown));
+        return;
+    }
+
+    // The boundaries only contain generators of lower dimension, whose diagrams they are checked
+    // against.
+    let Diagram::DiagramN(diagram) = diagram else {
+        return;
+    };
+    for boundary in [Boundary::Source, Boundary::Target] {
+        let slice = diagram.slice(boundary).unwrap();
+        if let Err(error) = typecheck(&slice, signature, Mode::Deep) {
+            errors.push(ValidationError::IllTyped(boundary, error));
+        }
+    }
+}
+
+fn depends_on_itself(
+    dependencies: &FastHashMap<Generator, Vec<Generator>>,
+    generator: Generator,
+) -> bool {
+    let mut visited = FastHashSet::default();
+    let mut stack = dependencies.get(&generator).cloned().unwrap_or_default();
+    while let Some(next) = stack.pop() {
+        if next == generator {
+            return true;
+        }
+        if visited.insert(next) {
+            stack.extend(dependencies.get(&next).into_iter().flatten());
+        }
+    }
+    false
+}
+
+/// The dual of a signature in dimension `k`, in which the cells of dimension at least `k` are
+/// reversed in dimension `k`.
+///
+/// The generators and their invertibility stay the same, so that a diagram reversed with
+/// [`Diagram::reverse_in`] is well-typed in the dual.
+pub fn dual(signature: &impl Signature, k: usize) -> SignatureBuilder {
+    let mut dual = SignatureBuilder::default();
+    for generator in signature.generators() {
+        let info = signature.generator_info(generator).unwrap();
+        dual.insert(
+            generator,
+            info.diagram().reverse_in(k),
+            info.is_invertible(),
+        );
+    }
+    dual
+}
+
+/// The dual of a signature in which the 1-cells are reversed.
+pub fn op(signature: &impl Signature) -> SignatureBuilder {
+    dual(signature, 1)
 }
 
-/// Helper struct for building signatures in tests and benchmarks.
+/// The dual of a signature in which the 2-cells are reversed.
+pub fn co(signature: &impl Signature) -> SignatureBuilder {
+    dual(signature, 2)
+}
+
+/// Helper struct for building signatures in tests and benchmarks, and for signatures computed from
+/// other signatures.
 #[derive(Clone, Debug, Default)]
 pub struct SignatureBuilder(Vec<GeneratorData>);
 
 #[derive(Clone, Debug)]
-pub struct GeneratorData(Generator, Diagram);
+pub struct GeneratorData(Generator, Diagram, bool);
 
 impl GeneratorInfo for GeneratorData {
     fn diagram(&self) -> &Diagram {
@@ -42,7 +224,27 @@ impl GeneratorInfo for GeneratorData {
     }
 
     fn is_invertible(&self) -> bool {
-        self.0.dimension > 0
+        self.2
+    }
+}
+
+impl SignatureBuilder {
+    /// Add a generator with the given diagram, replacing the diagram of the generator if it is
+    /// already in the signature.
+    pub fn insert(&mut self, generator: Generator, diagram: impl Into<Diagram>, invertible: bool) {
+        let data = GeneratorData(generator, diagram.into(), invertible);
+        match self.0.iter_mut().find(|data| data.0 == generator) {
+            Some(existing) => *existing = data,
+            None => self.0.push(data),
+        }
+    }
+
+    pub(crate) fn next_id(&self) -> usize {
+        self.0
+            .iter()
+            .map(|data| data.0.id + 1)
+            .max()
+            .unwrap_or_default()
     }
 }
 
@@ -54,13 +256,17 @@ impl Signature for SignatureBuilder {
     }
 
     fn generator_info(&self, g: Generator) -> Option<&GeneratorData> {
-        self.0.get(g.id)
+        // Generators added by the builder are stored at their id.
+        self.0
+            .get(g.id)
+            .filter(|data| data.0 == g)
+            .or_else(|| self.0.iter().find(|data| data.0 == g))
     }
 
     fn add_zero(&mut self) -> Diagram0 {
-        let generator = Generator::new(self.0.len(), 0);
+        let generator = Generator::new(self.next_id(), 0);
         let diagram = Diagram0::from(generator);
-        self.0.push(GeneratorData(generator, diagram.into()));
+        self.0.push(GeneratorData(generator, diagram.into(), false));
         diagram
     }
 
@@ -71,10 +277,10 @@ impl Signature for SignatureBuilder {
     ) -> Result<DiagramN, NewDiagramError> {
         let source: Diagram = source.into();
         let target: Diagram = target.into();
-        let generator = Generator::new(self.0.len(), source.dimension() + 1);
+        let generator = Generator::new(self.next_id(), source.dimension() + 1);
         let diagram = DiagramN::from_generator(generator, source, target)?;
         self.0
-            .push(GeneratorData(generator, diagram.clone().into()));
+            .push(GeneratorData(generator, diagram.clone().into(), true));
         Ok(diagram)
     }
 }
@@ -0,0 +1,159 @@
+//! Tracking points of diagrams through the moves that change them, so that a point picked out in
+//! a diagram, such as the slice being viewed, can be found again after the diagram is edited.
+//!
+//! A point is a sequence of slice indices, one for each dimension from the top. Points in a
+//! boundary are left alone by rewrites, since rewrites restrict to identities on the boundary.
+//! Where a point has several candidate images, the lowest one is chosen.
+use crate::{
+    common::{Boundary, BoundaryPath, Height, SliceIndex},
+    rewrite::Cone,
+    Cospan, Diagram, DiagramN, Direction, Rewrite, RewriteN,
+};
+
+type Point = Vec<SliceIndex>;
+
+/// The image of a point of the source of a rewrite in its target.
+pub fn image(rewrite: &Rewrite, point: &[SliceIndex]) -> Point {
+    use Height::{Regular, Singular};
+
+    let (Rewrite::RewriteN(rewrite), Some((&SliceIndex::Interior(height), rest))) =
+        (rewrite, point.split_first())
+    else {
+        return point.to_vec();
+    };
+
+    let (height, rest) = match height {
+        Singular(i) => (
+            Singular(rewrite.singular_image(i)),
+            image(&rewrite.slice(i), rest),
+        ),
+        Regular(i) => {
+            let targets = rewrite.regular_preimage(i);
+            if targets.is_empty() {
+                // The regular height is inside a cone, so it is sent to the target of the cone.
+                let (target, cone) = cone_containing(rewrite, i);
+                (
+                    Singular(target),
+                    image(&cone.regular_slices()[i - cone.index], rest),
+                )
+            } else {
+                (Regular(targets.start), rest.to_vec())
+            }
+        }
+    };
+
+    lift(height, rest)
+}
+
+/// A point of the source of a rewrite whose image is the given point of its target.
+///
+/// When the point lies over the target of a cone with no source, the preimage is the regular
+/// height that the cone is inserted at.
+pub fn preimage(rewrite: &Rewrite, point: &[SliceIndex]) -> Point {
+    use Height::{Regular, Singular};
+
+    let (Rewrite::RewriteN(rewrite), Some((&SliceIndex::Interior(height), rest))) =
+        (rewrite, point.split_first())
+    else {
+        return point.to_vec();
+    };
+
+    let (height, rest) = match height {
+        Regular(j) => (Regular(rewrite.regular_image(j)), rest.to_vec()),
+        Singular(j) => {
+            let sources = rewrite.singular_preimage(j);
+            if sources.is_empty() {
+                let cone = rewrite.cone_over_target(j).unwrap();
+                (
+                    Regular(sources.start),
+                    preimage(&cone.regular_slices()[0], rest),
+                )
+            } else {
+                (
+                    Singular(sources.start),
+                    preimage(&rewrite.slice(sources.start), rest),
+                )
+            }
+        }
+    };
+
+    lift(height, rest)
+}
+
+/// Track a point across a cospan, from the source of the forward rewrite to the source of the
+/// backward rewrite if the direction is forward, and the other way round otherwise.
+pub fn across(cospan: &Cospan, direction: Direction, point: &[SliceIndex]) -> Point {
+    match direction {
+        Direction::Forward => preimage(&cospan.backward, &image(&cospan.forward, point)),
+        Direction::Backward => preimage(&cospan.forward, &image(&cospan.backward, point)),
+    }
+}
+
+/// Track a point of the source of a diagram through each of its cospans to its target.
+///
+/// This follows points through the result of a contraction, expansion or attachment performed on
+/// the identity of a diagram, as the moves on the workspace are.
+pub fn source_to_target(diagram: &DiagramN, point: &[SliceIndex]) -> Point {
+    diagram
+        .cospans()
+        .iter()
+        .fold(point.to_vec(), |point, cospan| {
+            across(cospan, Direction::Forward, &point)
+        })
+}
+
+/// Track a point of a diagram into the result of attaching `added` cospans to it at a boundary
+/// path, as [`DiagramN::attach`] and the homotopies on boundaries do.
+///
+/// The old boundary becomes a regular slice of the result, so a point in it is sent to that
+/// slice rather than to the new boundary.
+pub fn attachment(
+    diagram: &DiagramN,
+    boundary_path: BoundaryPath,
+    added: usize,
+    point: &[SliceIndex],
+) -> Point {
+    use Height::{Regular, Singular};
+
+    let depth = boundary_path.depth();
+    let Some(&index) = point.get(depth) else {
+        return point.to_vec();
+    };
+
+    let index = match (boundary_path.boundary(), index) {
+        (Boundary::Source, SliceIndex::Boundary(Boundary::Source)) => Regular(added).into(),
+        (Boundary::Source, SliceIndex::Interior(Regular(i))) => Regular(i + added).into(),
+        (Boundary::Source, SliceIndex::Interior(Singular(i))) => Singular(i + added).into(),
+        (Boundary::Target, SliceIndex::Boundary(Boundary::Target)) => {
+            let slice = point[..depth]
+                .iter()
+                .try_fold(Diagram::from(diagram.clone()), |slice, index| {
+                    DiagramN::try_from(slice).ok()?.slice(*index)
+                });
+            match slice {
+                Some(Diagram::DiagramN(slice)) => Regular(slice.size()).into(),
+                _ => index,
+            }
+        }
+        _ => index,
+    };
+
+    let mut point = point.to_vec();
+    point[depth] = index;
+    point
+}
+
+fn lift(height: Height, mut rest: Point) -> Point {
+    rest.insert(0, height.into());
+    rest
+}
+
+/// The cone of a rewrite with a regular height strictly inside it, along with its target.
+fn cone_containing(rewrite: &RewriteN, height: usize) -> (usize, &Cone) {
+    rewrite
+        .targets()
+        .into_iter()
+        .zip(rewrite.cones())
+        .find(|(_, cone)| cone.index < height && height < cone.index + cone.len())
+        .unwrap()
+}
@@ -1,5 +1,4 @@
 use std::{
-    cell::RefCell,
     convert::{Into, TryInto},
     rc::Rc,
 };
@@ -19,6 +18,7 @@ use crate::{
     diagram::{Diagram, DiagramN},
     rewrite::{Cone, Cospan, Rewrite, RewriteN},
     scaffold::{Explodable, Scaffold},
+    session,
     signature::{GeneratorInfo, Signature},
     Rewrite0, SliceIndex,
 };
@@ -30,12 +30,51 @@ pub enum TypeError {
     #[error("diagram contains an unknown generator: {0:?}")]
     UnknownGenerator(Generator),
 
-    #[error("diagram is ill-typed")]
-    IllTyped,
+    /// The neighbourhood of the point at the given coordinates does not match the diagram of the
+    /// generator at that point.
+    #[error("diagram is ill-typed at {point:?}: neighbourhood does not match {generator:?}")]
+    IllTyped {
+        point: Vec<Height>,
+        generator: Generator,
+        neighbourhood: Diagram,
+    },
+
+    /// The generator at the point with the given coordinates is of too high a dimension to
+    /// appear there.
+    #[error("diagram is ill-typed at {point:?}: {generator:?} has the wrong dimension")]
+    WrongDimension {
+        point: Vec<Height>,
+        generator: Generator,
+    },
 }
 
-thread_local! {
-    static RESTRICT_CACHE: RefCell<FastHashMap<(Rewrite, Embedding), Rewrite>> = RefCell::new(FastHashMap::default());
+impl TypeError {
+    /// Lift the location of the error to the diagram which has the current diagram as a slice at
+    /// the given height.
+    fn lift(self, height: Height) -> Self {
+        match self {
+            Self::UnknownGenerator(_) => self,
+            Self::IllTyped {
+                mut point,
+                generator,
+                neighbourhood,
+            } => {
+                point.insert(0, height);
+                Self::IllTyped {
+                    point,
+                    generator,
+                    neighbourhood,
+                }
+            }
+            Self::WrongDimension {
+                mut point,
+                generator,
+            } => {
+                point.insert(0, height);
+                Self::WrongDimension { point, generator }
+            }
+        }
+    }
 }
 
 pub fn typecheck(
@@ -43,8 +82,8 @@ pub fn typecheck(
     signature: &impl Signature,
     mode: Mode,
 ) -> Result<(), TypeError> {
-    if !check_dimension(diagram.clone()) {
-        return Err(TypeError::IllTyped);
+    if let Some((point, generator)) = check_dimension(diagram.clone()) {
+        return Err(TypeError::WrongDimension { point, generator });
     }
 
     typecheck_worker(diagram, signature, mode)
@@ -60,24 +99,27 @@ fn typecheck_worker(
             if d.generator.dimension == 0 {
                 return Ok(());
             } else {
-                return Err(TypeError::IllTyped);
+                return Err(TypeError::WrongDimension {
+                    point: vec![],
+                    generator: d.generator,
+                });
             }
         }
         Diagram::DiagramN(d) => d,
     };
 
     if Mode::Deep == mode {
-        typecheck_worker(&diagram.source(), signature, mode)?;
+        typecheck_worker(&diagram.source(), signature, mode)
+            .map_err(|e| e.lift(Height::Regular(0)))?;
     }
 
     let slices: IdxVec<Height, Diagram> = diagram.slices().collect();
 
     for (i, cospan) in diagram.cospans().iter().enumerate() {
-        let target_embeddings = target_points(&[cospan.forward.clone(), cospan.backward.clone()])
-            .into_iter()
-            .map(|(t, g)| (Embedding::from_point(&t), g));
+        let target_points = target_points(&[cospan.forward.clone(), cospan.backward.clone()]);
 
-        for (target_embedding, generator) in target_embeddings {
+        for (point, generator) in target_points {
+            let target_embedding = Embedding::from_point(&point);
             let source = restrict_diagram(
                 &slices[Height::Regular(i)],
                 &target_embedding.preimage(&cospan.forward),
@@ -91,17 +133,29 @@ fn typecheck_worker(
                 .ok_or(TypeError::UnknownGenerator(generator))?
                 .diagram();
 
-            if collapse_simplicies(restricted) != collapse_simplicies(signature_diagram.clone()) {
-                return Err(TypeError::IllTyped);
+            if collapse_simplicies(restricted.clone())
+                != collapse_simplicies(signature_diagram.clone())
+            {
+                return Err(TypeError::IllTyped {
+                    point: std::iter::once(Height::Singular(i))
+                        .chain(point.into_iter().map(Height::Singular))
+                        .collect(),
+                    generator,
+                    neighbourhood: restricted.into(),
+                });
             }
         }
     }
 
-    RESTRICT_CACHE.with(|cache| cache.borrow_mut().clear());
+    clear_restrict_cache();
 
     Ok(())
 }
 
+/// Typecheck the target of a cospan out of a well-typed source.
+///
+/// Only the target of the cospan is checked, so the points of any errors are relative to the
+/// target, i.e. the contracted or expanded diagram, and not to the source.
 pub fn typecheck_cospan(
     source: Diagram,
     cospan: Cospan,
@@ -119,7 +173,7 @@ pub fn typecheck_cospan(
     Ok(())
 }
 
-fn target_points(rewrites: &[Rewrite]) -> Vec<(Point, Generator)> {
+pub(crate) fn target_points(rewrites: &[Rewrite]) -> Vec<(Point, Generator)> {
     if rewrites.is_empty() {
         return vec![];
     }
@@ -175,8 +229,10 @@ fn target_points(rewrites: &[Rewrite]) -> Vec<(Point, Generator)> {
     targets
 }
 
+/// A subdiagram given by an interval of heights in each dimension, described by the embedding of
+/// the singular slices it covers.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Embedding {
+pub(crate) enum Embedding {
     Regular(usize, Rc<Self>),
     Singular(usize, Vec<Rc<Self>>),
     Zero,
@@ -232,7 +288,8 @@ impl Embedding {
     }
 }
 
-fn restrict_diagram(diagram: &Diagram, embedding: &Embedding) -> Diagram {
+/// Restrict a diagram to a subdiagram.
+pub(crate) fn restrict_diagram(diagram: &Diagram, embedding: &Embedding) -> Diagram {
     match embedding {
         Embedding::Zero => {
             assert_eq!(diagram.dimension(), 0);
@@ -264,20 +321,18 @@ fn restrict_diagram(diagram: &Diagram, embedding: &Embedding) -> Diagram {
     }
 }
 
+/// Forget the rewrites restricted so far.
+pub(crate) fn clear_restrict_cache() {
+    session::clear_restrictions();
+}
+
 /// Restrict a rewrite to the preimage over the a subdiagram of the target.
 fn restrict_rewrite(rewrite: &Rewrite, embedding: &Embedding) -> Rewrite {
     if rewrite.is_identity() {
         return rewrite.clone();
     }
 
-    let cached = RESTRICT_CACHE.with(|cache| {
-        cache
-            .borrow()
-            .get(&(rewrite.clone(), embedding.clone()))
-            .cloned()
-    });
-
-    if let Some(cached) = cached {
+    if let Some(cached) = session::restriction(rewrite, embedding) {
         return cached;
     }
 
@@ -347,44 +402,47 @@ fn restrict_rewrite(rewrite: &Rewrite, embedding: &Embedding) -> Rewrite {
             let restricted_rewrite: Rewrite =
                 RewriteN::new(rewrite.dimension(), restricted_cones).into();
 
-            RESTRICT_CACHE.with(|cache| {
-                cache.borrow_mut().insert(
-                    (rewrite.clone().into(), embedding.clone()),
-                    restricted_rewrite.clone(),
-                )
-            });
+            session::cache_restriction(
+                rewrite.clone().into(),
+                embedding.clone(),
+                restricted_rewrite.clone(),
+            );
 
             restricted_rewrite
         }
     }
 }
 
-fn check_dimension(diagram: Diagram) -> bool {
+/// Find a point of the diagram whose generator is of too high a dimension to appear there.
+fn check_dimension(diagram: Diagram) -> Option<(Vec<Height>, Generator)> {
     fn worker(
         diagram: Diagram,
         max_dimension: usize,
         checked: &mut FastHashMap<DiagramN, usize>,
-    ) -> bool {
+    ) -> Option<(Vec<Height>, Generator)> {
         match diagram {
-            Diagram::Diagram0(d) => d.generator.dimension <= max_dimension,
+            Diagram::Diagram0(d) => {
+                (d.generator.dimension > max_dimension).then(|| (vec![], d.generator))
+            }
             Diagram::DiagramN(diagram) => {
                 if checked
                     .get(&diagram)
                     .map_or(false, |checked| *checked <= max_dimension)
                 {
-                    return true;
+                    return None;
                 }
 
-                if !diagram
-                    .slices()
-                    .enumerate()
-                    .all(|(i, slice)| worker(slice, max_dimension + i % 2, checked))
-                {
-                    return false;
+                for (i, slice) in diagram.slices().enumerate() {
+                    if let Some((mut point, generator)) =
+                        worker(slice, max_dimension + i % 2, checked)
+                    {
+                        point.insert(0, Height::from(i));
+                        return Some((point, generator));
+                    }
                 }
 
                 checked.insert(diagram, max_dimension);
-                true
+                None
             }
         }
     }
@@ -498,4 +556,32 @@ mod test {
 
         typecheck(&a.into(), &sig, Mode::Deep).unwrap();
     }
+
+    #[test]
+    fn located_error() {
+        let mut sig = SignatureBuilder::default();
+        let x = sig.add_zero();
+        let f = sig.add(x, x).unwrap();
+        let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+        let m = sig.add(ff.clone(), f.clone()).unwrap();
+
+        // the same generators, but with the multiplication reversed
+        let mut op = SignatureBuilder::default();
+        let x = op.add_zero();
+        let f = op.add(x, x).unwrap();
+        op.add(f, ff).unwrap();
+
+        match typecheck(&m.clone().into(), &op, Mode::Deep) {
+            Err(TypeError::IllTyped {
+                point,
+                generator,
+                neighbourhood,
+            }) => {
+                assert_eq!(point, vec![Height::Singular(0), Height::Singular(0)]);
+                assert_eq!(generator, Generator::new(2, 2));
+                assert_eq!(neighbourhood, m.into());
+            }
+            result => panic!("expected a located type error, got {result:?}"),
+        }
+    }
 }
@@ -0,0 +1,164 @@
+use homotopy_core::{
+    antipushout::{antipushout, factorize_inc, monotone, AntipushoutError},
+    examples,
+    signature::Signature,
+    Boundary, Diagram, DiagramN, Direction, Height, Rewrite, RewriteN,
+};
+use itertools::Itertools;
+use test_case::test_case;
+
+#[test_case(1, 1, 1)]
+#[test_case(1, 3, 1)]
+#[test_case(2, 2, 2)]
+#[test_case(2, 3, 3)]
+#[test_case(3, 3, 6)]
+fn base_lattice_paths(a: usize, b: usize, count: usize) {
+    let spans = monotone::antipushout_base(a, b);
+    assert_eq!(spans.len(), count);
+
+    for (h, k) in spans {
+        assert_eq!(h.len(), a + b - 1);
+        assert_eq!(k.len(), a + b - 1);
+        assert_eq!((h[0], k[0]), (0, 0));
+        assert_eq!((h[h.len() - 1], k[k.len() - 1]), (a - 1, b - 1));
+        for i in 1..h.len() {
+            // every step advances exactly one side
+            assert_eq!(h[i] - h[i - 1] + k[i] - k[i - 1], 1);
+        }
+    }
+}
+
+#[test]
+fn base_disconnected() {
+    assert_eq!(monotone::antipushout_base(0, 0).len(), 0);
+    assert_eq!(monotone::antipushout_base(0, 2).len(), 0);
+    assert_eq!(monotone::antipushout_base(0, 1).len(), 1);
+}
+
+#[test]
+fn monotone_antipushout() -> anyhow::Result<()> {
+    // [0, 0] -> [1] <- [0] glues both heights of the left into one
+    let spans = monotone::antipushout(&vec![0, 0].into(), &vec![0].into(), 1)?;
+    assert_eq!(spans, vec![(vec![0, 1].into(), vec![0, 0].into())]);
+
+    // [0, 1] -> [2] <- [1] only shares the second height
+    let spans = monotone::antipushout(&vec![0, 1].into(), &vec![1].into(), 2)?;
+    assert_eq!(spans, vec![(vec![1].into(), vec![0].into())]);
+
+    // a height hit by both sides but not connected is not a pushout
+    let spans = monotone::antipushout(&vec![0, 0].into(), &vec![].into(), 1)?;
+    assert_eq!(spans.len(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn monotone_out_of_range() {
+    assert!(matches!(
+        monotone::antipushout(&vec![0, 1].into(), &vec![0].into(), 1),
+        Err(AntipushoutError::OutOfRange)
+    ));
+}
+
+// an unbiased contraction of two heights is a pushout of their singular slices over the regular
+// slice between them, so the antipushout of the contraction should recover that span
+#[test_case(examples::two_beads())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::stacks())]
+fn contraction_span((sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    use Height::{Regular, Singular};
+
+    let contracted = diagram.clone().identity().contract(
+        Boundary::Target.into(),
+        &mut [],
+        0,
+        Direction::Forward,
+        None,
+        &sig,
+    )?;
+    let contract: RewriteN = contracted.cospans()[0].forward.clone().try_into()?;
+    let target: DiagramN = contracted.target().try_into()?;
+
+    let slice = |diagram: &DiagramN, height| -> Diagram { diagram.slice(height).unwrap() };
+    let spans = antipushout(
+        &slice(&diagram, Singular(0)),
+        &slice(&diagram, Singular(1)),
+        &slice(&target, Singular(0)),
+        &contract.slice(0),
+        &contract.slice(1),
+    )?;
+
+    let backward = &diagram.cospans()[0].backward;
+    let forward = &diagram.cospans()[1].forward;
+    assert!(spans.iter().any(|(s, h, k)| {
+        *s == slice(&diagram, Regular(1))
+            && h.equals_modulo_labels(backward)
+            && k.equals_modulo_labels(forward)
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn mismatched_dimensions() {
+    let (_sig, diagram) = examples::two_beads();
+
+    // the second side of the cospan is a slice of the first
+    let result = antipushout(
+        &diagram.clone().into(),
+        &diagram.source(),
+        &diagram.clone().into(),
+        &diagram.cospans()[0].forward,
+        &diagram.cospans()[0].forward,
+    );
+    assert!(matches!(result, Err(AntipushoutError::Dimension)));
+}
+
+// the inclusion inserts exactly the heights outside the image of the rewrite, and the rest of the
+// rewrite factors through it
+#[test_case(examples::scalar())]
+#[test_case(examples::two_scalars())]
+#[test_case(examples::two_beads())]
+#[test_case(examples::monoid_unit())]
+#[test_case(examples::scalar_and_beads())]
+#[test_case(examples::snake())]
+fn factorize_inc_cospans((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    use Height::{Regular, Singular};
+
+    for (i, cospan) in diagram.cospans().iter().enumerate() {
+        let singular = diagram.slice(Singular(i)).unwrap();
+        for (j, rewrite) in [(i, &cospan.forward), (i + 1, &cospan.backward)] {
+            let regular = diagram.slice(Regular(j)).unwrap();
+            let (p, q) = factorize_inc(&regular, &singular, rewrite)?;
+            assert!(p.compose(&q)?.equals_modulo_labels(rewrite));
+
+            let (Rewrite::RewriteN(f), Rewrite::RewriteN(p), Rewrite::RewriteN(q)) =
+                (rewrite, p, q)
+            else {
+                continue;
+            };
+            let size = DiagramN::try_from(regular)?.size();
+            let image = f.singular_monotone(size).slices().dedup().collect_vec();
+            assert!(p
+                .singular_monotone(size)
+                .slices()
+                .dedup()
+                .eq(0..image.len()));
+            assert!(q.singular_monotone(image.len()).slices().eq(image));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn factorize_inc_mismatched_dimensions() {
+    let (_sig, diagram) = examples::two_beads();
+
+    let result = factorize_inc(
+        &diagram.source(),
+        &diagram.clone().into(),
+        &diagram.cospans()[0].forward,
+    );
+    assert!(matches!(result, Err(AntipushoutError::Dimension)));
+}
@@ -0,0 +1,104 @@
+use homotopy_core::{
+    braid::{braid, crossing, parse_word, BraidError},
+    examples,
+    common::Mode,
+    invariants::Invariants,
+    signature::{Signature, SignatureBuilder},
+    typecheck::typecheck,
+    Boundary, DiagramN,
+};
+use test_case::test_case;
+
+fn scalar() -> (SignatureBuilder, DiagramN) {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let s = sig.add(x.identity(), x.identity()).unwrap();
+    (sig, s)
+}
+
+#[test]
+fn parse() {
+    assert_eq!(parse_word("s1 s2^-1  s1").unwrap(), vec![1, -2, 1]);
+    assert_eq!(parse_word("s3^2 s1^-2").unwrap(), vec![3, 3, -1, -1]);
+    assert_eq!(parse_word("s2^0").unwrap(), Vec::<isize>::new());
+    assert_eq!(parse_word("").unwrap(), Vec::<isize>::new());
+    assert!(matches!(parse_word("s0"), Err(BraidError::Parse(_))));
+    assert!(matches!(parse_word("t1"), Err(BraidError::Parse(_))));
+    assert!(matches!(parse_word("s1^x"), Err(BraidError::Parse(_))));
+}
+
+// the strands change places across a crossing, even when they are different
+#[test_case(true ; "positive")]
+#[test_case(false ; "negative")]
+fn crossing_swaps_strands(positive: bool) -> anyhow::Result<()> {
+    let (sig, slice) = examples::two_scalars();
+    let cospan = crossing(&slice, 0, positive, &sig)?;
+    let diagram = DiagramN::new(slice.clone().into(), vec![cospan]);
+    typecheck(&diagram.clone().into(), &sig, Mode::Deep)?;
+
+    let mut cospans = slice.cospans().to_vec();
+    cospans.swap(0, 1);
+    assert_eq!(diagram.target(), DiagramN::new(slice.source(), cospans).into());
+    Ok(())
+}
+
+// the braid word can be read back off the diagram
+#[test_case(2, "" ; "trivial")]
+#[test_case(2, "s1" ; "crossing")]
+#[test_case(2, "s1^-1" ; "inverse crossing")]
+#[test_case(3, "s1 s2^-1 s1" ; "three strands")]
+#[test_case(4, "s1 s3 s2^2" ; "four strands")]
+fn round_trip(strands: usize, word: &str) -> anyhow::Result<()> {
+    let (sig, s) = scalar();
+    let word = parse_word(word)?;
+    let diagram = braid(&s, strands, &word, None, &sig)?;
+    assert_eq!(diagram.size(), word.len());
+    typecheck(&diagram.clone().into(), &sig, Mode::Deep)?;
+
+    let invariants = Invariants::new(&diagram.into())?;
+    assert_eq!(invariants.braid_word(), Some(word.as_slice()));
+    assert_eq!(invariants.components(), strands);
+    Ok(())
+}
+
+// each crossing is an instance of the braiding generator or its inverse
+#[test]
+fn braiding_generator() -> anyhow::Result<()> {
+    let (mut sig, s) = scalar();
+    let pair = s.attach(&s, Boundary::Target, &[])?;
+    let beta = sig.add(pair.clone(), pair)?;
+
+    let diagram = braid(&s, 3, &[1, -2], Some(&beta), &sig)?;
+    typecheck(&diagram.clone().into(), &sig, Mode::Deep)?;
+
+    let slice = braid(&s, 3, &[], None, &sig)?.source();
+    let expected = DiagramN::try_from(slice)?
+        .identity()
+        .attach(&beta, Boundary::Target, &[0])?
+        .attach(&beta.inverse(), Boundary::Target, &[1])?;
+    assert_eq!(diagram, expected);
+    Ok(())
+}
+
+#[test]
+fn braiding_boundary() {
+    let (mut sig, s) = scalar();
+    let cell = sig.add(s.clone(), s.clone()).unwrap();
+    assert!(matches!(
+        braid(&s, 2, &[1], Some(&cell), &sig),
+        Err(BraidError::Braiding)
+    ));
+}
+
+#[test]
+fn strands() {
+    let (sig, s) = scalar();
+    assert!(matches!(
+        braid(&s, 2, &[2], None, &sig),
+        Err(BraidError::Strands(2, 2))
+    ));
+    assert!(matches!(
+        braid(&s, 0, &[], None, &sig),
+        Err(BraidError::NoStrands)
+    ));
+}
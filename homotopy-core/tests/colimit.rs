@@ -0,0 +1,206 @@
+use homotopy_core::{
+    colimit::{colimit, pushout, ColimitError},
+    common::Mode,
+    contraction::ContractionError,
+    examples,
+    scaffold::{Scaffold, ScaffoldNode},
+    signature::Signature,
+    Cospan, Diagram, DiagramN, Height, Rewrite,
+};
+use test_case::test_case;
+
+// checks that the legs of the cocone are well-formed and commute with the rewrites of the graph
+fn assert_cocone(graph: &Scaffold<Vec<Height>>) -> anyhow::Result<Diagram> {
+    let cocone = colimit(graph)?;
+
+    for (n, node) in graph.node_indices().zip(graph.node_weights()) {
+        let leg = &cocone.legs[n];
+        if let Diagram::DiagramN(diagram) = &node.diagram {
+            let witness = DiagramN::new(
+                diagram.clone().into(),
+                vec![Cospan {
+                    forward: leg.clone(),
+                    backward: Rewrite::identity(diagram.dimension()),
+                }],
+            );
+            assert!(witness.check(Mode::Deep).is_ok(), "malformed leg");
+            assert_eq!(witness.target(), cocone.colimit);
+        }
+    }
+
+    for edge in graph.edge_indices() {
+        let (s, t) = graph.edge_endpoints(edge).unwrap();
+        let composite = graph[edge].rewrite.compose(&cocone.legs[t])?;
+        assert!(composite.equals_modulo_labels(&cocone.legs[s]));
+    }
+
+    Ok(cocone.colimit)
+}
+
+#[test_case(examples::two_monoid())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::snake())]
+#[test_case(examples::lips())]
+fn single_node((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let n = graph.add_node(ScaffoldNode::new(vec![], diagram.clone()));
+
+    let cocone = colimit(&graph)?;
+    assert_eq!(cocone.colimit, diagram.into());
+    assert!(cocone.legs[n].is_identity());
+
+    Ok(())
+}
+
+// the colimit of a cospan is its apex
+#[test_case(examples::two_monoid())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::stacks())]
+#[test_case(examples::snake())]
+#[test_case(examples::associator())]
+fn colimit_of_cospan((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    for (i, cospan) in diagram.cospans().iter().enumerate() {
+        let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+        let mut add = |height| {
+            graph.add_node(ScaffoldNode::new(
+                vec![height],
+                diagram.slice(height).unwrap(),
+            ))
+        };
+        let r0 = add(Height::Regular(i));
+        let s = add(Height::Singular(i));
+        let r1 = add(Height::Regular(i + 1));
+        graph.add_edge(r0, s, cospan.forward.clone().into());
+        graph.add_edge(r1, s, cospan.backward.clone().into());
+
+        let colimit = assert_cocone(&graph)?;
+        assert_eq!(colimit, diagram.slice(Height::Singular(i)).unwrap());
+    }
+
+    Ok(())
+}
+
+// pushing out along an identity does nothing
+#[test_case(examples::two_monoid())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::snake())]
+fn pushout_identity((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    let a = diagram.slice(Height::Regular(0)).unwrap();
+    let b = diagram.slice(Height::Singular(0)).unwrap();
+    let f = diagram.cospans()[0].forward.clone();
+    let id = Rewrite::identity(f.dimension());
+
+    let (colimit, a_leg, b_leg) = pushout(&a, &b, &a, &id, &f)?;
+    assert_eq!(colimit, b);
+    assert!(a_leg.equals_modulo_labels(&f));
+    assert!(b_leg.is_identity());
+
+    Ok(())
+}
+
+// the contraction of two levels is a colimit
+#[test_case(examples::three_beads())]
+#[test_case(examples::stacks())]
+fn colimit_of_zigzag((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let nodes: Vec<_> = Height::for_size(2)
+        .map(|height| {
+            graph.add_node(ScaffoldNode::new(
+                vec![height],
+                diagram.slice(height).unwrap(),
+            ))
+        })
+        .collect();
+    for (i, cospan) in diagram.cospans()[..2].iter().enumerate() {
+        graph.add_edge(
+            nodes[2 * i],
+            nodes[2 * i + 1],
+            cospan.forward.clone().into(),
+        );
+        graph.add_edge(
+            nodes[2 * i + 2],
+            nodes[2 * i + 1],
+            cospan.backward.clone().into(),
+        );
+    }
+
+    assert_cocone(&graph)?;
+
+    Ok(())
+}
+
+#[test]
+fn colimit_ambiguous() {
+    let (_sig, diagram) = examples::two_scalars();
+
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let nodes: Vec<_> = Height::for_size(2)
+        .map(|height| {
+            graph.add_node(ScaffoldNode::new(
+                vec![height],
+                diagram.slice(height).unwrap(),
+            ))
+        })
+        .collect();
+    for (i, cospan) in diagram.cospans().iter().enumerate() {
+        graph.add_edge(
+            nodes[2 * i],
+            nodes[2 * i + 1],
+            cospan.forward.clone().into(),
+        );
+        graph.add_edge(
+            nodes[2 * i + 2],
+            nodes[2 * i + 1],
+            cospan.backward.clone().into(),
+        );
+    }
+
+    assert!(matches!(
+        colimit(&graph),
+        Err(ColimitError::NoColimit(ContractionError::Ambiguous))
+    ));
+}
+
+#[test]
+fn colimit_errors() {
+    let (_sig, diagram) = examples::two_monoid();
+    let source = diagram.source();
+    let target = diagram.target();
+
+    let graph: Scaffold<Vec<Height>> = Scaffold::default();
+    assert!(matches!(colimit(&graph), Err(ColimitError::Empty)));
+
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    graph.add_node(ScaffoldNode::new(vec![Height::Regular(0)], source.clone()));
+    graph.add_node(ScaffoldNode::new(vec![Height::Regular(1)], target.clone()));
+    assert!(matches!(colimit(&graph), Err(ColimitError::Disconnected)));
+
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let s = graph.add_node(ScaffoldNode::new(vec![Height::Regular(0)], source.clone()));
+    graph.add_node(ScaffoldNode::new(vec![Height::Regular(1)], diagram.clone()));
+    graph.add_edge(s, s, Rewrite::identity(1).into());
+    assert!(matches!(colimit(&graph), Err(ColimitError::Dimension)));
+
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let s = graph.add_node(ScaffoldNode::new(vec![Height::Regular(0)], source.clone()));
+    graph.add_edge(s, s, Rewrite::identity(1).into());
+    assert!(matches!(colimit(&graph), Err(ColimitError::Cyclic)));
+
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let s = graph.add_node(ScaffoldNode::new(vec![Height::Regular(0)], source.clone()));
+    let t = graph.add_node(ScaffoldNode::new(vec![Height::Regular(1)], target.clone()));
+    graph.add_edge(s, t, Rewrite::identity(1).into());
+    assert!(matches!(colimit(&graph), Err(ColimitError::Malformed(0))));
+
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let s = graph.add_node(ScaffoldNode::new(vec![Height::Regular(0)], source.clone()));
+    let t = graph.add_node(ScaffoldNode::new(vec![Height::Regular(0)], target.clone()));
+    graph.add_edge(s, t, diagram.cospans()[0].forward.clone().into());
+    assert!(matches!(colimit(&graph), Err(ColimitError::Coordinates)));
+
+    let mut graph: Scaffold<Vec<Height>> = Scaffold::default();
+    let s = graph.add_node(ScaffoldNode::new(vec![Height::Regular(0)], source));
+    let t = graph.add_node(ScaffoldNode::new(vec![], target));
+    graph.add_edge(s, t, diagram.cospans()[0].forward.clone().into());
+    assert!(matches!(colimit(&graph), Err(ColimitError::Coordinates)));
+}
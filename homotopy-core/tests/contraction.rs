@@ -103,6 +103,42 @@ fn scalar() {
     );
 }
 
+#[test]
+fn contract_all_scalars() -> anyhow::Result<()> {
+    let (sig, scalar) = examples::scalar();
+    let two_scalars = scalar.attach(&scalar, Boundary::Target, &[])?;
+
+    let options = two_scalars.clone().identity().contract_all(
+        Boundary::Target.into(),
+        &[],
+        0,
+        Direction::Forward,
+        &sig,
+    )?;
+
+    // the contraction is ambiguous, so only the biased options remain
+    let biases: Vec<_> = options.iter().map(|(bias, _)| *bias).collect();
+    assert_eq!(biases, vec![Some(Bias::Higher), Some(Bias::Lower)]);
+    for (bias, diagram) in options {
+        let expected = two_scalars.clone().identity().contract(
+            Boundary::Target.into(),
+            &mut [],
+            0,
+            Direction::Forward,
+            bias,
+            &sig,
+        )?;
+        assert_eq!(diagram, expected);
+    }
+
+    assert!(scalar
+        .identity()
+        .contract_all(Boundary::Target.into(), &[], 0, Direction::Forward, &sig)
+        .is_err());
+
+    Ok(())
+}
+
 #[test]
 fn three_scalars() {
     let (sig, scalar) = examples::scalar();
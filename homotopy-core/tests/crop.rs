@@ -0,0 +1,102 @@
+use homotopy_core::{
+    common::Mode,
+    crop::CropError,
+    examples,
+    signature::{Signature, SignatureBuilder},
+    Boundary, DiagramN, Height,
+};
+use test_case::test_case;
+
+#[test_case(examples::associator())]
+#[test_case(examples::two_monoid())]
+#[test_case(examples::two_scalars())]
+#[test_case(examples::crossing())]
+#[test_case(examples::half_braid())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::stacks())]
+#[test_case(examples::matchsticks())]
+#[test_case(examples::snake())]
+#[test_case(examples::lips())]
+#[test_case(examples::pants_unit())]
+fn crop_everything((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    let bounds = [Height::Regular(0)..=Height::Regular(diagram.size())];
+    assert_eq!(diagram.crop(&bounds)?, diagram);
+
+    Ok(())
+}
+
+// cropping around any single height of any slice gives a well-formed subdiagram
+#[test_case(examples::associator())]
+#[test_case(examples::two_monoid())]
+#[test_case(examples::crossing())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::stacks())]
+#[test_case(examples::matchsticks())]
+#[test_case(examples::snake())]
+#[test_case(examples::lips())]
+#[test_case(examples::pants_unit())]
+fn crop_well_formed((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    for h0 in Height::for_size(diagram.size()) {
+        let slice = DiagramN::try_from(diagram.slice(h0).unwrap())?;
+        for h1 in Height::for_size(slice.size()) {
+            let cropped = diagram.crop(&[h0..=h0, h1..=h1])?;
+            assert!(cropped.check(Mode::Deep).is_ok(), "malformed crop");
+            assert!(cropped.size() <= diagram.size());
+        }
+    }
+
+    Ok(())
+}
+
+// | |      |
+// c |      c
+// | b  ->  |
+// a |      a
+// | |      |
+#[allow(clippy::many_single_char_names)]
+#[test]
+fn crop_wire() -> anyhow::Result<()> {
+    use Height::{Regular, Singular};
+
+    let mut sig = SignatureBuilder::default();
+
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let a = sig.add(f.clone(), f.clone()).unwrap();
+    let b = sig.add(f.clone(), f.clone()).unwrap();
+    let c = sig.add(f.clone(), f.clone()).unwrap();
+
+    let diagram = f
+        .attach(&f, Boundary::Target, &[])?
+        .identity()
+        .attach(&a, Boundary::Target, &[0])?
+        .attach(&b, Boundary::Target, &[1])?
+        .attach(&c, Boundary::Target, &[0])?;
+
+    // The crop keeps the level of b as an identity, which normalisation removes.
+    let cropped = diagram.crop(&[Regular(0)..=Regular(3), Singular(0)..=Singular(0)])?;
+    assert_eq!(cropped.size(), 3);
+    assert_eq!(cropped.normalize().0, a.attach(&c, Boundary::Target, &[])?);
+
+    // The second wire only, where it passes through b.
+    assert_eq!(
+        diagram.crop(&[Singular(1)..=Singular(1), Singular(1)..=Singular(1)])?,
+        b
+    );
+
+    Ok(())
+}
+
+#[test]
+fn crop_errors() {
+    let (_sig, diagram) = examples::two_monoid();
+
+    assert!(matches!(
+        diagram.crop(&vec![Height::Regular(0)..=Height::Regular(0); 3]),
+        Err(CropError::Dimension)
+    ));
+    assert!(matches!(
+        diagram.crop(&[Height::Regular(5)..=Height::Regular(6)]),
+        Err(CropError::Empty)
+    ));
+}
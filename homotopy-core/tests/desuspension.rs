@@ -0,0 +1,62 @@
+use homotopy_core::{
+    desuspension::{Desuspension, DesuspensionError},
+    examples, Diagram, Diagram0, DiagramN, Generator,
+};
+use test_case::test_case;
+
+fn base() -> (Generator, Generator) {
+    (Generator::new(100, 0), Generator::new(101, 0))
+}
+
+#[test_case(examples::scalar().1 ; "scalar")]
+#[test_case(examples::two_monoid().1 ; "two monoid")]
+#[test_case(examples::associator().1 ; "associator")]
+#[test_case(examples::crossing().1 ; "crossing")]
+#[test_case(examples::snake().1 ; "snake")]
+#[test_case(examples::lips().1 ; "lips")]
+fn round_trip(diagram: DiagramN) {
+    let (s, t) = base();
+    let desuspension = Desuspension::new(s, t);
+    let suspended: Diagram = diagram.suspend(s, t).into();
+    assert_eq!(
+        desuspension.apply(&suspended).unwrap(),
+        Diagram::from(diagram)
+    );
+}
+
+#[test]
+fn points() {
+    let (s, t) = base();
+    let (_, point) = examples::one_zero_cell();
+    let suspended: Diagram = point.suspend(s, t).into();
+    assert_eq!(
+        Desuspension::new(s, t).apply(&suspended).unwrap(),
+        point.into()
+    );
+}
+
+#[test]
+fn not_suspended() {
+    let (s, t) = base();
+    let desuspension = Desuspension::new(s, t);
+    let (_, diagram) = examples::two_monoid();
+    assert!(matches!(
+        desuspension.apply(&diagram.into()),
+        Err(DesuspensionError::NotSuspended(_))
+    ));
+    assert!(matches!(
+        desuspension.apply(&Diagram0::from(s).into()),
+        Err(DesuspensionError::Dimension)
+    ));
+
+    // the base generators must only appear as the endpoints of the suspension
+    assert!(matches!(
+        desuspension.apply(&Diagram0::from(s).identity().into()),
+        Err(DesuspensionError::NotSuspended(generator)) if generator == s
+    ));
+    let (_, scalar) = examples::scalar();
+    assert!(matches!(
+        Desuspension::new(t, s).apply(&scalar.suspend(s, t).into()),
+        Err(DesuspensionError::NotSuspended(_))
+    ));
+}
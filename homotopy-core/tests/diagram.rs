@@ -1,7 +1,12 @@
+use homotopy_common::hash::hash_one;
 use homotopy_core::{
+    check::ModuloLabels,
+    common::BoundaryPath,
+    diagram::{AttachmentError, PastingError},
     examples,
+    factorization::rewrites,
     signature::{GeneratorInfo, Signature},
-    Boundary, Generator,
+    Boundary, Cospan, Diagram, DiagramN, Generator, Height,
 };
 use insta::assert_debug_snapshot;
 
@@ -38,3 +43,154 @@ fn monoid_with_unit_to_id() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn compose_vertical() -> anyhow::Result<()> {
+    let (_sig, assoc) = examples::associator();
+    let composed = assoc.compose(&assoc.inverse(), 0)?;
+    assert_eq!(composed.size(), 2 * assoc.size());
+    assert_eq!(composed.source(), assoc.source());
+    assert_eq!(composed.target(), assoc.source());
+    Ok(())
+}
+
+// ensure pasting two monoids side by side
+//  |   |      |   |
+//   \ /        \ /
+//    |          |
+// agrees with whiskering the first and attaching the second
+#[test]
+fn compose_horizontal() -> anyhow::Result<()> {
+    let (_sig, m) = examples::two_monoid();
+    let wire: DiagramN = m.target().try_into()?;
+
+    let composed = m.compose(&m, 1)?;
+    let whiskered = m
+        .attach(&wire, Boundary::Target, &[])?
+        .attach(&wire, Boundary::Target, &[])?
+        .attach(&m, Boundary::Target, &[1])?;
+    assert_eq!(composed, whiskered);
+    Ok(())
+}
+
+#[test]
+fn compose_mismatch() {
+    let (_sig, m) = examples::two_monoid();
+    // the single wire of the target ends where the source has a second wire
+    assert!(matches!(
+        m.compose(&m, 0),
+        Err(PastingError::BoundaryMismatch(
+            BoundaryPath(Boundary::Target, 0),
+            Some(Height::Singular(1))
+        ))
+    ));
+    assert!(matches!(m.compose(&m, 2), Err(PastingError::Depth(2, 2))));
+
+    let wire: DiagramN = m.target().try_into().unwrap();
+    assert!(matches!(
+        m.compose(&wire, 0),
+        Err(PastingError::Dimension(_))
+    ));
+}
+
+// the multiplication rebuilt from an unlabelled rewrite differs only in its labels
+#[test]
+fn equals_modulo_labels() {
+    let (_sig, diagram) = examples::two_monoid();
+    let cospan = &diagram.cospans()[0];
+    let singular = diagram.slice(Height::Singular(0)).unwrap();
+
+    let forward = rewrites(diagram.source(), singular, 2).next().unwrap();
+    assert_ne!(forward, cospan.forward);
+
+    let relabelled = DiagramN::new(
+        diagram.source(),
+        vec![Cospan {
+            forward,
+            backward: cospan.backward.clone(),
+        }],
+    );
+    assert_ne!(relabelled, diagram);
+    assert!(relabelled.equals_modulo_labels(&diagram));
+    assert!(!diagram.equals_modulo_labels(&diagram.inverse()));
+
+    let hash = |d: &DiagramN| hash_one(ModuloLabels(Diagram::from(d.clone())));
+    assert_eq!(
+        ModuloLabels(relabelled.clone()),
+        ModuloLabels(diagram.clone())
+    );
+    assert_eq!(hash(&relabelled), hash(&diagram));
+    assert_ne!(hash(&diagram), hash(&diagram.inverse()));
+}
+
+#[test]
+fn attach_everywhere_beads() -> anyhow::Result<()> {
+    let (_sig, e) = examples::two_endomorphism();
+    let wire: DiagramN = e.source().try_into()?;
+    let wires = wire
+        .attach(&wire, Boundary::Target, &[])?
+        .attach(&wire, Boundary::Target, &[])?;
+
+    let everywhere = wires
+        .clone()
+        .identity()
+        .attach_everywhere(&e, Boundary::Target)?;
+    let one_by_one = wires
+        .clone()
+        .identity()
+        .attach(&e, Boundary::Target, &[2])?
+        .attach(&e, Boundary::Target, &[1])?
+        .attach(&e, Boundary::Target, &[0])?;
+    assert_eq!(everywhere, one_by_one);
+    assert_eq!(everywhere.target(), wires.into());
+    Ok(())
+}
+
+// of the three matches of the multiplication on four wires, the middle one overlaps the others
+#[test]
+fn attach_everywhere_disjoint() -> anyhow::Result<()> {
+    let (_sig, m) = examples::two_monoid();
+    let wires: DiagramN = m.source().try_into()?;
+    let four_wires = wires.attach(&wires, Boundary::Target, &[])?;
+
+    let everywhere = four_wires
+        .identity()
+        .attach_everywhere(&m, Boundary::Target)?;
+    assert_eq!(everywhere.size(), 2);
+    assert_eq!(everywhere.target(), wires.into());
+
+    assert!(matches!(
+        DiagramN::try_from(m.target())?
+            .identity()
+            .attach_everywhere(&m, Boundary::Target),
+        Err(AttachmentError::IncompatibleAttachment)
+    ));
+    Ok(())
+}
+
+// units fit into every regular height, including the ends
+#[test]
+fn attach_everywhere_units() -> anyhow::Result<()> {
+    let (sig, _) = examples::monoid_unit();
+    let wire: DiagramN = sig
+        .generator_info(Generator::new(1, 1))
+        .unwrap()
+        .diagram()
+        .clone()
+        .try_into()?;
+    let unit: DiagramN = sig
+        .generator_info(Generator::new(3, 2))
+        .unwrap()
+        .diagram()
+        .clone()
+        .try_into()?;
+    let wires = wire.attach(&wire, Boundary::Target, &[])?;
+
+    let everywhere = wires
+        .clone()
+        .identity()
+        .attach_everywhere(&unit, Boundary::Target)?;
+    assert_eq!(everywhere.size(), 3);
+    assert_eq!(DiagramN::try_from(everywhere.target())?.size(), 5);
+    Ok(())
+}
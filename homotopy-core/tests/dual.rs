@@ -0,0 +1,65 @@
+use homotopy_core::{
+    common::Mode,
+    examples,
+    signature::{dual, op, GeneratorInfo, Signature, SignatureBuilder},
+    typecheck::typecheck,
+    Boundary, Diagram, DiagramN,
+};
+use test_case::test_case;
+
+#[test_case(examples::two_monoid() ; "two monoid")]
+#[test_case(examples::associator() ; "associator")]
+#[test_case(examples::monoid_unit() ; "monoid unit")]
+#[test_case(examples::snake() ; "snake")]
+#[test_case(examples::lips() ; "lips")]
+#[test_case(examples::crossing() ; "crossing")]
+#[test_case(examples::two_cups() ; "two cups")]
+fn reverse(example: (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    let (sig, diagram) = example;
+    for k in 1..=diagram.dimension() {
+        let reversed = diagram.reverse_in(k);
+        assert_eq!(reversed.reverse_in(k), diagram);
+        typecheck(&reversed.clone().into(), &dual(&sig, k), Mode::Deep)?;
+
+        if k == diagram.dimension() {
+            assert_eq!(reversed.source(), diagram.target());
+            assert_eq!(reversed.target(), diagram.source());
+        } else {
+            assert_eq!(reversed.source(), diagram.source().reverse_in(k));
+            assert_eq!(reversed.target(), diagram.target().reverse_in(k));
+        }
+    }
+    Ok(())
+}
+
+// the mirror image of the left unit law is the right unit law
+#[test]
+fn unit_laws() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    let ff = f.attach(&f, Boundary::Target, &[])?;
+    let m = sig.add(ff, f.clone())?;
+    let u = sig.add(x.identity(), f)?;
+    let left = m.attach(&u, Boundary::Source, &[0])?;
+    let right = m.attach(&u, Boundary::Source, &[1])?;
+
+    let mirror = left.reverse_in(1);
+    typecheck(&mirror.clone().into(), &op(&sig), Mode::Deep)?;
+    assert!(Diagram::from(mirror).equals_modulo_labels(&right.into()));
+    Ok(())
+}
+
+// the dual keeps the generators of the signature and their invertibility
+#[test]
+fn dual_signature() {
+    let (sig, _) = examples::two_monoid();
+    let dual = dual(&sig, 1);
+    assert!(sig.generators().eq(dual.generators()));
+    for generator in sig.generators() {
+        let info = sig.generator_info(generator).unwrap();
+        let dual_info = dual.generator_info(generator).unwrap();
+        assert_eq!(info.is_invertible(), dual_info.is_invertible());
+        assert_eq!(&info.diagram().reverse_in(1), dual_info.diagram());
+    }
+}
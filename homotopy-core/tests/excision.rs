@@ -0,0 +1,94 @@
+use homotopy_core::{
+    excision::ExcisionError,
+    signature::{Signature, SignatureBuilder},
+    Boundary, DiagramN, Height,
+};
+
+// | |      | |
+// c |      c |
+// | b  ->  a |
+// a |      | |
+// | |
+#[allow(clippy::many_single_char_names)]
+fn beads() -> (impl Signature, DiagramN, DiagramN) {
+    let mut sig = SignatureBuilder::default();
+
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let a = sig.add(f.clone(), f.clone()).unwrap();
+    let b = sig.add(f.clone(), f.clone()).unwrap();
+    let c = sig.add(f.clone(), f.clone()).unwrap();
+
+    let a_f = a.attach(&f, Boundary::Target, &[]).unwrap();
+    let diagram = a_f
+        .attach(&b, Boundary::Target, &[1])
+        .unwrap()
+        .attach(&c, Boundary::Target, &[0])
+        .unwrap();
+    let excised = a_f.attach(&c, Boundary::Target, &[0]).unwrap();
+
+    (sig, diagram, excised)
+}
+
+#[test]
+fn excise_middle() -> anyhow::Result<()> {
+    use Height::Singular;
+
+    let (sig, diagram, excised) = beads();
+    assert_eq!(diagram.excise(&[Singular(1), Singular(1)], &sig)?, excised);
+
+    Ok(())
+}
+
+#[test]
+fn excise_top() -> anyhow::Result<()> {
+    use Height::Singular;
+
+    let (sig, diagram, _) = beads();
+    assert_eq!(
+        diagram.excise(&[Singular(2), Singular(0)], &sig)?,
+        diagram.behead(2)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn excise_not_a_cell() {
+    use Height::{Regular, Singular};
+
+    let (sig, diagram, _) = beads();
+    assert!(matches!(
+        diagram.excise(&[Regular(1), Singular(0)], &sig),
+        Err(ExcisionError::NotACell)
+    ));
+    // the wire next to a
+    assert!(matches!(
+        diagram.excise(&[Singular(0), Singular(1)], &sig),
+        Err(ExcisionError::NotACell)
+    ));
+}
+
+// e
+// e
+// e
+#[test]
+fn excise_sandwiched() {
+    use Height::Singular;
+
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let e = sig.add(f.clone(), f).unwrap();
+    let diagram = e
+        .attach(&e, Boundary::Target, &[])
+        .unwrap()
+        .attach(&e, Boundary::Target, &[])
+        .unwrap();
+
+    assert!(matches!(
+        diagram.excise(&[Singular(1), Singular(0)], &sig),
+        Err(ExcisionError::NotOnBoundary)
+    ));
+    assert!(diagram.excise(&[Singular(0), Singular(0)], &sig).is_ok());
+}
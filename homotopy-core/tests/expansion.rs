@@ -1,5 +1,8 @@
 use homotopy_core::{
+    common::Location,
+    contraction::ContractionError,
     examples,
+    expansion::ExpansionError,
     signature::{GeneratorInfo, Signature},
     Bias, Boundary, DiagramN, Direction, Generator, Height,
 };
@@ -186,7 +189,7 @@ fn braid_smooth() -> anyhow::Result<()> {
         .try_into()?;
     assert_eq!(expanded.cospans().len(), 0);
     let f = DiagramN::try_from(
-        sig.generator_info(Generator::new(1, 1))
+        sig.generator_info(Generator::new(1, 2))
             .unwrap()
             .diagram()
             .clone(),
@@ -212,17 +215,34 @@ fn bead_no_stretch() -> anyhow::Result<()> {
     let e_f_f_inverse =
         e.attach(&f, Boundary::Target, &[])?
             .attach(&f.inverse(), Boundary::Target, &[])?;
-    assert!(e_f_f_inverse
-        .identity()
-        .contract(
+    let location = Location {
+        depth: 0,
+        heights: vec![0],
+        cones: vec![0],
+    };
+    assert!(matches!(
+        e_f_f_inverse.clone().identity().contract(
             Boundary::Target.into(),
             &mut [Height::Singular(0)],
             1,
             Direction::Forward,
             None,
             &sig
-        )
-        .is_err());
+        ),
+        Err(ContractionError::Invalid(l)) if l == location
+    ));
+    // the same failure one level down
+    assert!(matches!(
+        e_f_f_inverse.identity().identity().contract(
+            Boundary::Target.into(),
+            &mut [Height::Regular(0), Height::Singular(0)],
+            1,
+            Direction::Forward,
+            None,
+            &sig
+        ),
+        Err(ContractionError::Invalid(l)) if l == Location { depth: 1, ..location.clone() }
+    ));
 
     let (mut sig, touching) = examples::touching();
     // make touching have only one singular height
@@ -239,7 +259,7 @@ fn bead_no_stretch() -> anyhow::Result<()> {
         .target()
         .try_into()?;
     let f = DiagramN::try_from(
-        sig.generator_info(Generator::new(1, 1))
+        sig.generator_info(Generator::new(1, 2))
             .unwrap()
             .diagram()
             .clone(),
@@ -258,15 +278,15 @@ fn bead_no_stretch() -> anyhow::Result<()> {
             &sig,
         )?
         .target();
-    assert!(e_touching
-        .identity()
-        .expand(
+    assert!(matches!(
+        e_touching.identity().expand(
             Boundary::Target.into(),
             &mut [Height::Singular(0)],
             [Height::Singular(1), Height::Singular(0)],
             Direction::Forward,
             &sig
-        )
-        .is_err());
+        ),
+        Err(ExpansionError::FailedToPropagate(l)) if l == location
+    ));
     Ok(())
 }
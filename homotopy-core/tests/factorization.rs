@@ -1,11 +1,13 @@
 use homotopy_core::{
-    common::{BoundaryPath, Label},
-    factorization::factorize,
+    common::{BoundaryPath, Label, Mode},
+    examples,
+    factorization::{factorize, rewrites},
     rewrite::Cone,
     signature::{Signature, SignatureBuilder},
     Boundary, Cospan, Diagram, DiagramN, Generator, Height, Rewrite, Rewrite0, RewriteN,
     SliceIndex,
 };
+use test_case::test_case;
 
 #[test]
 #[allow(clippy::many_single_char_names)]
@@ -286,3 +288,58 @@ fn scalar_braid() {
 
     assert!(fact.next().is_some());
 }
+
+// every cospan of a diagram is found among the rewrites between its slices, and every rewrite
+// found is well-formed and goes between the slices
+#[test_case(examples::two_monoid())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::stacks())]
+#[test_case(examples::snake())]
+#[test_case(examples::associator())]
+fn rewrites_of_cospans((_sig, diagram): (impl Signature, DiagramN)) {
+    for (i, cospan) in diagram.cospans().iter().enumerate() {
+        let regular = diagram.slice(Height::Regular(i)).unwrap();
+        let singular = diagram.slice(Height::Singular(i)).unwrap();
+
+        let found: Vec<Rewrite> = rewrites(regular.clone(), singular.clone(), 3).collect();
+        assert!(found
+            .iter()
+            .any(|r| r.equals_modulo_labels(&cospan.forward)));
+
+        for rewrite in found {
+            assert!(rewrite.check(Mode::Shallow).is_ok(), "malformed rewrite");
+            if let Diagram::DiagramN(regular) = &regular {
+                let witness = DiagramN::new(
+                    regular.clone().into(),
+                    vec![Cospan {
+                        forward: rewrite,
+                        backward: Rewrite::identity(regular.dimension()),
+                    }],
+                );
+                assert_eq!(witness.target(), singular);
+            }
+        }
+    }
+}
+
+#[test_case(examples::two_monoid())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::snake())]
+fn rewrites_include_identity((_sig, diagram): (impl Signature, DiagramN)) {
+    let source = diagram.source();
+    assert!(rewrites(source.clone(), source, 1).any(|r| r.is_identity()));
+}
+
+#[test]
+fn rewrites_bounded() {
+    let (_sig, diagram) = examples::two_monoid();
+    let regular = diagram.source();
+    let singular = diagram.slice(Height::Singular(0)).unwrap();
+
+    // The multiplication merges two wires, so needs a cone of size 2.
+    assert_eq!(rewrites(regular.clone(), singular.clone(), 2).count(), 1);
+    assert_eq!(rewrites(regular.clone(), singular.clone(), 1).count(), 0);
+
+    // There is no degeneracy going the other way.
+    assert_eq!(rewrites(singular, regular, 2).count(), 0);
+}
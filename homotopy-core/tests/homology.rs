@@ -0,0 +1,100 @@
+use homotopy_core::{
+    examples,
+    homology::{signature_homology, stratum_homology, HomologyError},
+    signature::{Signature, SignatureBuilder},
+    Boundary, Diagram, Generator,
+};
+use test_case::test_case;
+
+fn betti(diagram: impl Into<Diagram>, generator: Generator) -> Vec<usize> {
+    stratum_homology(&diagram.into(), generator)
+        .unwrap()
+        .betti_numbers
+}
+
+#[test_case(Generator::new(0, 0), &[1, 0, 0] ; "regions")]
+#[test_case(Generator::new(1, 1), &[1, 0] ; "wire")]
+#[test_case(Generator::new(2, 2), &[1] ; "vertex")]
+#[test_case(Generator::new(3, 2), &[0] ; "missing")]
+fn endomorphism_strata(generator: Generator, expected: &[usize]) {
+    let (_, diagram) = examples::two_endomorphism();
+    assert_eq!(betti(diagram, generator), expected);
+}
+
+// the wire of a bubble closes up into a circle
+#[test]
+fn bubble_is_circle() {
+    let (_, diagram) = examples::bubble();
+    let homology = stratum_homology(&diagram.into(), Generator::new(1, 1)).unwrap();
+    assert_eq!(homology.betti_numbers, vec![1, 1]);
+    assert_eq!(homology.euler_characteristic(), 0);
+}
+
+#[test]
+fn snake_is_interval() {
+    let (_, diagram) = examples::snake();
+    assert_eq!(betti(diagram, Generator::new(1, 1)), vec![1, 0]);
+}
+
+#[test]
+fn stratum_dimension() {
+    let (_, diagram) = examples::lips();
+    assert!(matches!(
+        stratum_homology(&diagram.into(), Generator::new(0, 0)),
+        Err(HomologyError::StratumDimension)
+    ));
+}
+
+#[test]
+fn torus_signature() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    let g = sig.add(x, x)?;
+    sig.add(
+        f.attach(&g, Boundary::Target, &[])?,
+        g.attach(&f, Boundary::Target, &[])?,
+    )?;
+
+    let homology = signature_homology(&sig)?;
+    assert_eq!(homology.betti_numbers, vec![1, 2, 1]);
+    assert_eq!(homology.euler_characteristic(), 0);
+    Ok(())
+}
+
+#[test]
+fn sphere_signature() -> anyhow::Result<()> {
+    let (sig, _) = examples::scalar();
+    assert_eq!(signature_homology(&sig)?.betti_numbers, vec![1, 0, 1]);
+    Ok(())
+}
+
+// the projective plane has the rational homology of a point, as its first homology is torsion
+#[test]
+fn projective_plane_signature() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    sig.add(f.attach(&f, Boundary::Target, &[])?, x.identity())?;
+
+    assert_eq!(signature_homology(&sig)?.betti_numbers, vec![1, 0, 0]);
+    Ok(())
+}
+
+// inverse cells count negatively, so the Klein bottle has no rational homology in degree 2
+#[test]
+fn klein_bottle_signature() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    let g = sig.add(x, x)?;
+    sig.add(
+        f.attach(&g, Boundary::Target, &[])?,
+        g.attach(&f.inverse(), Boundary::Target, &[])?,
+    )?;
+
+    let homology = signature_homology(&sig)?;
+    assert_eq!(homology.betti_numbers, vec![1, 1, 0]);
+    assert_eq!(homology.euler_characteristic(), 0);
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use homotopy_core::{
+    examples,
+    index::EmbeddingIndex,
+    signature::{GeneratorInfo, Signature, SignatureBuilder},
+    Boundary, Diagram, DiagramN, Generator,
+};
+use test_case::test_case;
+
+fn boundaries(sig: &impl Signature) -> Vec<((Generator, Boundary), Diagram)> {
+    let mut boundaries = Vec::new();
+    for generator in sig.generators() {
+        let diagram = sig.generator_info(generator).unwrap().diagram();
+        if let Diagram::DiagramN(diagram) = diagram {
+            for boundary in [Boundary::Source, Boundary::Target] {
+                boundaries.push(((generator, boundary), diagram.slice(boundary).unwrap()));
+            }
+        }
+    }
+    boundaries
+}
+
+// the index finds the same embeddings as searching for every boundary by brute force
+#[test_case(examples::two_monoid())]
+#[test_case(examples::monoid_unit())]
+#[test_case(examples::scalar_and_beads())]
+#[test_case(examples::snake())]
+#[test_case(examples::crossing())]
+#[test_case(examples::pants_unit())]
+fn index_agrees_with_embeddings((sig, diagram): (impl Signature, DiagramN)) {
+    let boundaries = boundaries(&sig);
+    let index: EmbeddingIndex<_> = boundaries.iter().cloned().collect();
+    assert_eq!(index.len(), boundaries.len());
+
+    for haystack in diagram.slices().chain([diagram.clone().into()]) {
+        let expected: Vec<_> = boundaries
+            .iter()
+            .flat_map(|(key, needle)| haystack.embeddings(needle).map(move |e| (*key, e)))
+            .collect();
+        let found: Vec<_> = index
+            .embeddings(&haystack)
+            .map(|(key, e)| (*key, e))
+            .collect();
+        assert_eq!(found, expected);
+    }
+}
+
+// only the bead on the wire that occurs is a candidate
+#[test]
+fn index_skips_missing_generators() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    let g = sig.add(x, x)?;
+    let a = sig.add(f.clone(), f.clone())?;
+    sig.add(g.clone(), g)?;
+
+    let index: EmbeddingIndex<_> = boundaries(&sig)
+        .into_iter()
+        .filter(|((generator, _), _)| generator.dimension == 2)
+        .collect();
+    assert_eq!(index.len(), 4);
+
+    let haystack = f.attach(&f, Boundary::Target, &[])?.into();
+    let keys: Vec<_> = index
+        .embeddings(&haystack)
+        .map(|((generator, _), _)| *generator)
+        .collect();
+    assert_eq!(keys.len(), 4);
+    assert!(keys
+        .iter()
+        .all(|generator| *generator == a.max_generator().generator));
+    Ok(())
+}
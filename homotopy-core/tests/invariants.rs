@@ -0,0 +1,64 @@
+use homotopy_core::{
+    examples,
+    invariants::Invariants,
+    layout::Layout,
+    projection::{Depths, Homotopy, Projection},
+    Cospan, Diagram, DiagramN,
+};
+
+#[test]
+fn wires_without_crossings() {
+    let (_, diagram) = examples::two_beads();
+    let invariants = Invariants::new(&diagram.into()).unwrap();
+    assert_eq!(invariants.crossings(), []);
+    assert_eq!(invariants.components(), 2);
+    assert_eq!(invariants.writhe(), 0);
+    assert_eq!(invariants.braid_word(), Some(&[][..]));
+}
+
+#[test]
+fn merging_wires_are_not_a_braid() {
+    let (_, diagram) = examples::two_monoid();
+    let invariants = Invariants::new(&diagram.into()).unwrap();
+    assert_eq!(invariants.components(), 1);
+    assert_eq!(invariants.braid_word(), None);
+}
+
+// two wires which touch at a half braid and separate again on the sides they came from
+fn touching_wires() -> DiagramN {
+    let (_, half_braid) = examples::half_braid();
+    let forward = half_braid.cospans()[0].forward.clone();
+    DiagramN::new(
+        half_braid.source(),
+        vec![Cospan {
+            forward: forward.clone(),
+            backward: forward,
+        }],
+    )
+}
+
+#[test]
+fn half_braids_are_not_crossings() {
+    let diagram: Diagram = touching_wires().into();
+    let layout = Layout::<2>::new(&diagram).unwrap();
+    let depths = Depths::<2>::new(&diagram).unwrap();
+    let projection = Projection::new(&diagram, &layout, &depths).unwrap();
+    assert_eq!(projection.crossings(), []);
+    assert!(layout
+        .keys()
+        .any(|&coord| matches!(projection.homotopy(coord), Some(Homotopy::HalfBraid))));
+}
+
+#[test]
+fn half_braids_join_wires() {
+    let invariants = Invariants::new(&touching_wires().into()).unwrap();
+    assert_eq!(invariants.crossings(), []);
+    assert_eq!(invariants.components(), 1);
+}
+
+#[test]
+fn dimension() {
+    let (_, diagram) = examples::two_monoid();
+    assert!(Invariants::new(&diagram.source()).is_err());
+    assert!(Invariants::new(&diagram.identity().identity().identity().into()).is_err());
+}
@@ -0,0 +1,98 @@
+use homotopy_core::{
+    common::Mode,
+    invariants::Invariants,
+    knot::{knot, parse_gauss, parse_pd, KnotError},
+    signature::{Signature, SignatureBuilder},
+    typecheck::typecheck,
+    DiagramN,
+};
+use test_case::test_case;
+
+// the suspension of an invertible scalar
+fn strand() -> (SignatureBuilder, DiagramN) {
+    let mut sig = SignatureBuilder::default();
+    let a = sig.add_zero();
+    let b = sig.add_zero();
+    let x = sig.add(a, b).unwrap();
+    let s = sig.add(x.clone().identity(), x.identity()).unwrap();
+    (sig, s)
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        parse_pd("X[1,4,2,5], X[3,6,4,1], X[5,2,6,3]").unwrap(),
+        vec![[1, 4, 2, 5], [3, 6, 4, 1], [5, 2, 6, 3]]
+    );
+    assert_eq!(parse_pd("").unwrap(), Vec::<[usize; 4]>::new());
+    assert!(matches!(parse_pd("X[1,2,3]"), Err(KnotError::Parse(_))));
+
+    assert_eq!(
+        parse_gauss("O1-U2-O3-U1-O2-U3-").unwrap(),
+        vec![[3, 6, 4, 1], [1, 4, 2, 5], [5, 2, 6, 3]]
+    );
+    assert!(matches!(parse_gauss("O1-U2-"), Err(KnotError::Parse(_))));
+    assert!(matches!(parse_gauss("O1-U1+"), Err(KnotError::Parse(_))));
+}
+
+// the writhe of the knot agrees with the signs of the crossings in the code
+#[test_case("", 1, 0 ; "unknot")]
+#[test_case("X[1,1,2,2]", 1, 1 ; "kink")]
+#[test_case("X[1,4,2,5], X[3,6,4,1], X[5,2,6,3]", 1, -3 ; "left trefoil")]
+#[test_case("X[1,5,2,4], X[3,1,4,6], X[5,3,6,2]", 1, 3 ; "right trefoil")]
+#[test_case("X[4,2,5,1], X[8,6,1,5], X[6,3,7,4], X[2,7,3,8]", 1, 0 ; "figure eight")]
+#[test_case("X[1,6,2,7], X[3,8,4,9], X[5,10,6,1], X[7,2,8,3], X[9,4,10,5]", 1, -5 ; "cinquefoil")]
+#[test_case("X[4,1,3,2], X[2,3,1,4]", 2, -2 ; "hopf link")]
+fn pd(code: &str, components: usize, writhe: isize) -> anyhow::Result<()> {
+    let (sig, s) = strand();
+    let code = parse_pd(code)?;
+    let diagram = knot(&s, &code, &sig)?;
+    assert_eq!(diagram.dimension(), 4);
+    typecheck(&diagram.clone().into(), &sig, Mode::Deep)?;
+
+    let invariants = Invariants::new(&diagram.into())?;
+    assert_eq!(invariants.crossings().len(), code.len());
+    assert_eq!(invariants.components(), components);
+    assert_eq!(invariants.writhe(), writhe);
+    Ok(())
+}
+
+#[test]
+fn gauss() -> anyhow::Result<()> {
+    let (sig, s) = strand();
+    let diagram = knot(&s, &parse_gauss("O1-U2-O3-U1-O2-U3-")?, &sig)?;
+    let invariants = Invariants::new(&diagram.into())?;
+    assert_eq!(invariants.components(), 1);
+    assert_eq!(invariants.writhe(), -3);
+    Ok(())
+}
+
+#[test]
+fn linking_number() -> anyhow::Result<()> {
+    let (sig, s) = strand();
+    let diagram = knot(&s, &parse_pd("X[4,1,3,2], X[2,3,1,4]")?, &sig)?;
+    let invariants = Invariants::new(&diagram.into())?;
+    assert_eq!(invariants.linking_number(0, 1), -1.0);
+    Ok(())
+}
+
+#[test]
+fn edges() {
+    let (sig, s) = strand();
+    assert!(matches!(
+        knot(&s, &[[1, 2, 3, 4]], &sig),
+        Err(KnotError::Edge(_))
+    ));
+}
+
+#[test]
+fn strands() {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let scalar = sig.add(x.identity(), x.identity()).unwrap();
+    assert!(matches!(knot(&scalar, &[], &sig), Err(KnotError::Strand)));
+
+    // the strand is not an invertible generator of this signature
+    let (_, s) = strand();
+    assert!(matches!(knot(&s, &[], &sig), Err(KnotError::Strand)));
+}
@@ -0,0 +1,214 @@
+use homotopy_core::{
+    common::Mode,
+    examples,
+    morphism::{MorphismError, SignatureMorphism},
+    signature::{GeneratorInfo, GeneratorReport, Signature, SignatureBuilder},
+    typecheck::typecheck,
+    Boundary, Diagram, Diagram0, DiagramN, Generator,
+};
+
+// | |      | |
+// b |      a |
+// | a  ->  | b
+// | |      | |
+#[allow(clippy::many_single_char_names)]
+fn beads() -> (impl Signature, DiagramN, DiagramN, DiagramN, DiagramN) {
+    let mut sig = SignatureBuilder::default();
+
+    let x = sig.add_zero();
+    let f = sig.add(x, x).unwrap();
+    let a = sig.add(f.clone(), f.clone()).unwrap();
+    let b = sig.add(f.clone(), f.clone()).unwrap();
+    let g = sig.add(x, x).unwrap();
+
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let diagram = ff
+        .identity()
+        .attach(&a, Boundary::Target, &[1])
+        .unwrap()
+        .attach(&b, Boundary::Target, &[0])
+        .unwrap();
+
+    (sig, diagram, a, b, g)
+}
+
+fn generator(diagram: &DiagramN) -> Generator {
+    diagram.max_generator().generator
+}
+
+#[test]
+fn rename_simultaneously() -> anyhow::Result<()> {
+    let (sig, diagram, a, b, _) = beads();
+    let ff = diagram.source();
+
+    let morphism = SignatureMorphism::new(
+        &sig,
+        &sig,
+        [
+            (generator(&a), b.clone().into()),
+            (generator(&b), a.clone().into()),
+        ],
+    )?;
+
+    let expected: Diagram = DiagramN::try_from(ff)?
+        .identity()
+        .attach(&b, Boundary::Target, &[1])?
+        .attach(&a, Boundary::Target, &[0])?
+        .into();
+    assert_eq!(morphism.apply(&diagram.into())?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn substitute_composite() -> anyhow::Result<()> {
+    let (sig, diagram, a, b, _) = beads();
+    let ff = DiagramN::try_from(diagram.source())?;
+
+    let bb = b.attach(&b, Boundary::Target, &[])?;
+    let morphism = SignatureMorphism::new(&sig, &sig, [(generator(&a), bb.clone().into())])?;
+
+    let expected: Diagram = ff
+        .identity()
+        .attach(&bb, Boundary::Target, &[1])?
+        .attach(&b, Boundary::Target, &[0])?
+        .into();
+    assert_eq!(morphism.apply(&diagram.into())?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn substitute_lower_dimension() -> anyhow::Result<()> {
+    let (sig, diagram, a, b, g) = beads();
+    let f = DiagramN::try_from(a.source())?;
+
+    let gg = g.attach(&g, Boundary::Target, &[])?;
+    let morphism = SignatureMorphism::new(&sig, &sig, [(generator(&f), gg.clone().into())])?;
+
+    // the beads are retyped to go between the images of their boundaries
+    let target = morphism.apply_signature();
+    let retyped = |bead: &DiagramN| -> anyhow::Result<DiagramN> {
+        let info = target.generator_info(generator(bead)).unwrap();
+        Ok(info.diagram().clone().try_into()?)
+    };
+    let (a, b) = (retyped(&a)?, retyped(&b)?);
+    assert_eq!(a.source(), gg.clone().into());
+    assert_eq!(b.target(), gg.clone().into());
+
+    let expected: Diagram = gg
+        .attach(&gg, Boundary::Target, &[])?
+        .identity()
+        .attach(&a, Boundary::Target, &[2])?
+        .attach(&b, Boundary::Target, &[0])?
+        .into();
+    let image = morphism.apply(&diagram.into())?;
+    assert_eq!(image, expected);
+    typecheck(&image, &target, Mode::Deep)?;
+
+    Ok(())
+}
+
+// the unit law of a monoid on f, instantiated at a monoid on g ⊗ g
+#[test]
+fn instantiate_monoid() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    let ff = f.attach(&f, Boundary::Target, &[])?;
+    let mult = sig.add(ff, f.clone())?;
+    let unit = sig.add(x.identity(), f.clone())?;
+    let left_unit = mult.attach(&unit, Boundary::Source, &[0])?;
+
+    let y = sig.add_zero();
+    let g = sig.add(y, y)?;
+    let gg = g.attach(&g, Boundary::Target, &[])?;
+    let gggg = gg.attach(&gg, Boundary::Target, &[])?;
+    let mu = sig.add(gggg, gg.clone())?;
+    let eta = sig.add(y.identity(), gg.clone())?;
+
+    let morphism = SignatureMorphism::new(
+        &sig,
+        &sig,
+        [
+            (x.generator, y.into()),
+            (generator(&f), gg.into()),
+            (generator(&mult), mu.clone().into()),
+            (generator(&unit), eta.clone().into()),
+        ],
+    )?;
+
+    let expected: Diagram = mu.attach(&eta, Boundary::Source, &[0])?.into();
+    assert_eq!(morphism.apply(&left_unit.into())?, expected);
+
+    Ok(())
+}
+
+// the snake is made of homotopies of an invertible 1-cell, which are found again for its image
+#[test]
+fn substitute_in_homotopy() -> anyhow::Result<()> {
+    let (mut sig, snake) = examples::snake();
+    let x = Diagram0::from(Generator::new(0, 0));
+    let f = Generator::new(1, 1);
+
+    let g = sig.add(x, x)?;
+    let gg = g.attach(&g, Boundary::Target, &[])?;
+    let morphism = SignatureMorphism::new(&sig, &sig, [(f, gg.clone().into())])?;
+
+    let image = DiagramN::try_from(morphism.apply(&snake.into())?)?;
+    assert_eq!(image.source(), gg.clone().into());
+    assert_eq!(image.target(), gg.into());
+    typecheck(&image.into(), &morphism.apply_signature(), Mode::Deep)?;
+
+    Ok(())
+}
+
+#[test]
+fn substitute_ill_typed() {
+    let (sig, _, a, _, g) = beads();
+
+    assert!(matches!(
+        SignatureMorphism::new(&sig, &sig, [(generator(&a), g.identity().into())]),
+        Err(MorphismError::IllTyped(_))
+    ));
+}
+
+// generators carried over from an independent signature do not clash with those of the target
+#[test]
+#[allow(clippy::many_single_char_names)]
+fn carry_into_independent_signature() -> anyhow::Result<()> {
+    let mut source = SignatureBuilder::default();
+    let x = source.add_zero();
+    let y = source.add_zero();
+    let f = source.add(x, y)?;
+
+    let mut target = SignatureBuilder::default();
+    let a = target.add_zero();
+    let b = target.add_zero();
+    let g = target.add(b, a)?;
+
+    let morphism = SignatureMorphism::new(
+        &source,
+        &target,
+        [(x.generator, a.into()), (y.generator, b.into())],
+    )?;
+
+    let carried = morphism.generator_image(generator(&f)).unwrap();
+    assert_ne!(carried.id, generator(&g).id);
+
+    let signature = morphism.apply_signature();
+    assert_eq!(signature.generators().count(), 4);
+    assert!(signature.validate().iter().all(GeneratorReport::is_valid));
+    assert_eq!(
+        signature.generator_info(generator(&g)).unwrap().diagram(),
+        &Diagram::from(g)
+    );
+
+    let image = DiagramN::try_from(morphism.apply(&f.into())?)?;
+    assert_eq!(image.max_generator().generator, carried);
+    assert_eq!(image.source(), a.into());
+    assert_eq!(image.target(), b.into());
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use homotopy_core::{
+    common::Mode, examples, rewrite::Cone, signature::Signature, Boundary, Cospan, Diagram,
+    DiagramN, Rewrite, RewriteN,
+};
+use test_case::test_case;
+
+// checks that the degeneracy is a well-formed rewrite from the normal form into the diagram
+fn assert_degeneracy(normal: &DiagramN, degeneracy: &RewriteN, diagram: &DiagramN) {
+    let witness = DiagramN::new(
+        normal.clone().into(),
+        vec![Cospan {
+            forward: degeneracy.clone().into(),
+            backward: Rewrite::identity(diagram.dimension()),
+        }],
+    );
+    assert!(witness.check(Mode::Deep).is_ok(), "malformed degeneracy");
+    assert_eq!(&DiagramN::try_from(witness.target()).unwrap(), diagram);
+}
+
+#[test_case(examples::associator())]
+#[test_case(examples::two_monoid())]
+#[test_case(examples::scalar())]
+#[test_case(examples::two_scalars())]
+#[test_case(examples::touching())]
+#[test_case(examples::crossing())]
+#[test_case(examples::half_braid())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::stacks())]
+#[test_case(examples::matchsticks())]
+#[test_case(examples::monoid_unit())]
+#[test_case(examples::snake())]
+#[test_case(examples::lips())]
+#[test_case(examples::pants_unit())]
+fn examples_are_normal((_sig, diagram): (impl Signature, DiagramN)) {
+    let (normal, degeneracy) = diagram.normalize();
+    assert_eq!(normal, diagram);
+    assert!(degeneracy.is_identity());
+    assert!(diagram.is_normal());
+}
+
+// padding by a weak identity on either side should be normalised away
+#[test_case(examples::two_monoid())]
+#[test_case(examples::scalar())]
+#[test_case(examples::snake())]
+#[test_case(examples::lips())]
+fn normalize_weak_identity((_sig, diagram): (impl Signature, DiagramN)) -> anyhow::Result<()> {
+    let padded = diagram
+        .source()
+        .weak_identity()
+        .attach(&diagram, Boundary::Target, &[])?
+        .attach(&diagram.target().weak_identity(), Boundary::Target, &[])?;
+    assert_eq!(padded.size(), diagram.size() + 2);
+
+    let (normal, degeneracy) = padded.normalize();
+    assert_eq!(normal, diagram);
+    assert_degeneracy(&normal, &degeneracy, &padded);
+
+    Ok(())
+}
+
+// a 2-diagram whose only singular slice is a weak identity on a point
+// should be normalised in both dimensions
+#[test]
+fn normalize_lower_dimension() {
+    let mut sig = homotopy_core::signature::SignatureBuilder::default();
+    let x: Diagram = sig.add_zero().into();
+
+    let weak = x.clone().weak_identity();
+    let unit: Rewrite = RewriteN::new(
+        1,
+        vec![Cone::new_unit(
+            0,
+            weak.cospans()[0].clone(),
+            Rewrite::identity(0),
+        )],
+    )
+    .into();
+    let diagram = DiagramN::new(
+        x.clone().identity().into(),
+        vec![Cospan {
+            forward: unit.clone(),
+            backward: unit,
+        }],
+    );
+
+    let (normal, degeneracy) = diagram.normalize();
+    assert_eq!(normal, x.identity().identity());
+    assert_degeneracy(&normal, &degeneracy, &diagram);
+}
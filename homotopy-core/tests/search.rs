@@ -0,0 +1,128 @@
+use homotopy_core::{
+    examples,
+    search::{moves, search, Budget, Move, SearchError},
+    signature::{GeneratorInfo, Signature},
+    Bias, Boundary, DiagramN, Direction, Generator, Height,
+};
+use test_case::test_case;
+
+// ensure the interchanger
+//  t      s
+//     ⤳
+//  s      t
+// is found by a contraction followed by an expansion
+#[test]
+fn interchange_scalars() -> anyhow::Result<()> {
+    let (sig, diagram) = examples::two_scalars();
+    let s: DiagramN = sig
+        .generator_info(Generator::new(1, 2))
+        .unwrap()
+        .diagram()
+        .clone()
+        .try_into()?;
+    let t: DiagramN = sig
+        .generator_info(Generator::new(2, 2))
+        .unwrap()
+        .diagram()
+        .clone()
+        .try_into()?;
+    let target = t.attach(&s, Boundary::Target, &[])?;
+
+    let moves = search(&diagram, &target, &sig, &Budget::default())?;
+    assert_eq!(moves.len(), 2);
+
+    let mut result = diagram;
+    for m in &moves {
+        result = m.apply(&result, &sig).unwrap();
+    }
+    assert_eq!(result, target);
+
+    Ok(())
+}
+
+#[test]
+fn search_trivial() -> anyhow::Result<()> {
+    let (sig, diagram) = examples::associator();
+    assert_eq!(
+        search(&diagram, &diagram, &sig, &Budget::default())?,
+        vec![]
+    );
+    Ok(())
+}
+
+#[test]
+fn search_budgets() {
+    let (sig, diagram) = examples::two_scalars();
+    let target = diagram.source().identity();
+
+    assert!(matches!(
+        search(
+            &diagram,
+            &examples::two_monoid().1,
+            &sig,
+            &Budget::default()
+        ),
+        Err(SearchError::Boundary)
+    ));
+
+    let budget = Budget {
+        depth: 0,
+        ..Budget::default()
+    };
+    assert!(matches!(
+        search(&diagram, &target, &sig, &budget),
+        Err(SearchError::NotFound)
+    ));
+
+    let budget = Budget {
+        nodes: 1,
+        ..Budget::default()
+    };
+    assert!(matches!(
+        search(&diagram, &target, &sig, &budget),
+        Err(SearchError::NodeBudget)
+    ));
+}
+
+fn two_scalars_identity() -> (impl Signature, DiagramN) {
+    let (sig, diagram) = examples::two_scalars();
+    (sig, diagram.identity())
+}
+
+// every enumerated move should agree with applying it through the public contraction and
+// expansion operations
+#[test_case(examples::two_scalars(), &[])]
+#[test_case(examples::two_beads(), &[])]
+#[test_case(examples::stacks(), &[])]
+#[test_case(two_scalars_identity(), &[Height::Regular(0)])]
+fn moves_agree_with_apply((sig, diagram): (impl Signature, DiagramN), location: &[Height]) {
+    let moves = moves(&diagram, location, &sig);
+    assert_ne!(moves.len(), 0);
+    for (m, result) in moves {
+        assert_eq!(m.apply(&diagram, &sig), Some(result));
+    }
+}
+
+#[test]
+fn moves_two_scalars() {
+    let (sig, diagram) = examples::two_scalars();
+    let contractions = moves(&diagram, &[], &sig);
+    assert!(contractions.iter().any(|(m, _)| *m
+        == Move::Contract {
+            location: vec![],
+            height: 0,
+            direction: Direction::Forward,
+            bias: Some(Bias::Lower),
+        }));
+    assert!(contractions
+        .iter()
+        .all(|(m, _)| matches!(m, Move::Contract { .. })));
+
+    // once contracted, the scalars can be expanded again
+    let (_, contracted) = &contractions[0];
+    let expansions: Vec<_> = moves(contracted, &[], &sig)
+        .into_iter()
+        .filter(|(m, _)| matches!(m, Move::Expand { .. }))
+        .collect();
+    assert_ne!(expansions.len(), 0);
+}
@@ -0,0 +1,124 @@
+use homotopy_core::{
+    collect_garbage, examples,
+    session::{limits, reset, set_limits, statistics, Limits, Statistics},
+    typecheck::{typecheck, Mode},
+    Diagram, DiagramN,
+};
+
+// each test runs on its own thread, and so in a fresh session
+#[test]
+fn statistics_are_swept() {
+    assert_eq!(statistics(), Statistics::default());
+
+    let (sig, diagram) = examples::associator();
+    let built = statistics();
+    assert!(built.diagrams > 0 && built.rewrites > 0 && built.cones > 0);
+
+    drop((sig, diagram));
+    collect_garbage();
+    assert_eq!(statistics(), Statistics::default());
+}
+
+#[test]
+fn parallel_sessions() {
+    let check = || {
+        let (sig, diagram) = examples::three_beads();
+        typecheck(&diagram.clone().into(), &sig, Mode::Deep).unwrap();
+        (format!("{diagram:?}"), statistics())
+    };
+
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4).map(|_| scope.spawn(check)).collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert!(results.iter().all(|result| *result == results[0]));
+
+    // the work done on other threads is not recorded in this session
+    assert_eq!(statistics(), Statistics::default());
+}
+
+#[test]
+fn sessions_are_not_mixed() {
+    let (_sig, diagram) = examples::scalar();
+    let elsewhere = std::thread::spawn(|| examples::scalar().1).join().unwrap();
+    assert_ne!(diagram, elsewhere);
+
+    // values move between sessions serialized
+    let moved: DiagramN =
+        serde_json::from_str(&serde_json::to_string(&elsewhere).unwrap()).unwrap();
+    assert_eq!(diagram, moved);
+}
+
+#[test]
+fn table_size_is_capped() {
+    let build = |n| {
+        let (_sig, diagram) = examples::bead_series(n);
+        Diagram::from(diagram).identity().identity()
+    };
+
+    for n in 1..20 {
+        build(n);
+    }
+    let uncapped = statistics().diagrams;
+    collect_garbage();
+
+    let cap = uncapped / 4;
+    set_limits(Limits {
+        table_size: Some(cap),
+        cache_size: None,
+    });
+    assert_eq!(limits().table_size, Some(cap));
+    // few values are in use, so the table is swept whenever it reaches its cap
+    for n in 1..20 {
+        build(n);
+        assert!(statistics().diagrams <= cap);
+    }
+}
+
+// a table with more values in use than half of its cap grows to twice its size after the last
+// sweep, which is at most the number of values in use
+#[test]
+fn table_size_is_bounded_by_values_in_use() {
+    let kept: Vec<Diagram> = (1..10).map(|n| examples::bead_series(n).1.into()).collect();
+    collect_garbage();
+    let in_use = statistics().diagrams;
+
+    let cap = in_use;
+    set_limits(Limits {
+        table_size: Some(cap),
+        cache_size: None,
+    });
+
+    // each tower of identities is in use until the next one is started
+    let height = 20;
+    let mut largest = 0;
+    for diagram in &kept {
+        let mut tower = diagram.clone();
+        for _ in 0..height {
+            tower = tower.identity().into();
+            let size = statistics().diagrams;
+            assert!(size <= cap.max(2 * (in_use + height)));
+            largest = largest.max(size);
+        }
+    }
+    assert!(largest > cap);
+}
+
+#[test]
+fn reset_starts_a_new_session() {
+    let capped = Limits {
+        table_size: Some(1000),
+        cache_size: None,
+    };
+    set_limits(capped);
+    let (_sig, diagram) = examples::scalar();
+
+    reset();
+    assert_eq!(statistics(), Statistics::default());
+    assert_eq!(limits(), capped);
+
+    // values from before the reset belong to the old session
+    let (_sig, again) = examples::scalar();
+    assert_ne!(diagram, again);
+    assert_eq!(again, examples::scalar().1);
+}
@@ -0,0 +1,100 @@
+use homotopy_core::{
+    examples,
+    signature::{GeneratorReport, Signature, SignatureBuilder, ValidationError},
+    Boundary, Diagram0, DiagramN, Generator,
+};
+use test_case::test_case;
+
+fn errors(sig: &SignatureBuilder, generator: Generator) -> Vec<ValidationError> {
+    sig.validate()
+        .into_iter()
+        .find(|report| report.generator == generator)
+        .unwrap()
+        .errors
+}
+
+#[test_case(examples::associator())]
+#[test_case(examples::two_monoid())]
+#[test_case(examples::crossing())]
+#[test_case(examples::snake())]
+#[test_case(examples::lips())]
+#[test_case(examples::pants_unit())]
+fn valid((sig, _diagram): (impl Signature, DiagramN)) {
+    assert!(sig.validate().iter().all(GeneratorReport::is_valid));
+}
+
+#[test]
+fn invalid() -> anyhow::Result<()> {
+    let x = Generator::new(0, 0);
+    let f = Generator::new(1, 1);
+    let g = Generator::new(1, 2);
+    let dangling = Generator::new(2, 1);
+    let looping = Generator::new(3, 1);
+    let nested = Generator::new(4, 1);
+    let unknown = Generator::new(5, 0);
+    let [px, punknown, plooping, pf] = [x, unknown, looping, f].map(Diagram0::from);
+
+    // the generators need not be well-formed
+    let mut sig = SignatureBuilder::default();
+    sig.insert(x, px, false);
+    // f shares its id with g
+    sig.insert(f, DiagramN::from_generator(f, px, px)?, false);
+    // g has a diagram of the wrong dimension
+    sig.insert(g, DiagramN::from_generator(f, px, px)?, false);
+    // unknown is not in the signature
+    sig.insert(
+        dangling,
+        DiagramN::from_generator(dangling, px, punknown)?,
+        false,
+    );
+    // looping is a point of its own boundary
+    sig.insert(
+        looping,
+        DiagramN::from_generator(looping, plooping, px)?,
+        false,
+    );
+    // the boundaries of nested are not points
+    sig.insert(nested, DiagramN::from_generator(nested, pf, pf)?, false);
+
+    let report = sig.validate();
+    assert!(report[0].is_valid());
+    assert!(matches!(
+        errors(&sig, f)[..],
+        [ValidationError::DuplicateId]
+    ));
+    assert!(matches!(
+        errors(&sig, g)[..],
+        [
+            ValidationError::DuplicateId,
+            ValidationError::Dimension(2, 1)
+        ]
+    ));
+    assert!(matches!(
+        errors(&sig, dangling)[..],
+        [ValidationError::Unknown(generator)] if generator == unknown
+    ));
+    assert!(matches!(
+        errors(&sig, nested)[..],
+        [
+            ValidationError::IllTyped(Boundary::Source, _),
+            ValidationError::IllTyped(Boundary::Target, _)
+        ]
+    ));
+    assert!(errors(&sig, looping)
+        .iter()
+        .any(|error| matches!(error, ValidationError::SelfDependent)));
+    Ok(())
+}
+
+#[test]
+fn generators_are_found_by_id_and_dimension() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+
+    assert!(sig.generator_info(x.generator).is_some());
+    assert!(sig.generator_info(f.max_generator().generator).is_some());
+    assert!(sig.generator_info(x.generator.suspended()).is_none());
+    assert!(sig.generator_info(Generator::new(1, 0)).is_none());
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use homotopy_core::{
+    common::BoundaryPath,
+    examples,
+    signature::{Signature, SignatureBuilder},
+    tracking::{across, attachment, image, preimage, source_to_target},
+    Bias, Boundary, Diagram, DiagramN, Direction, Height, SliceIndex,
+};
+use test_case::test_case;
+
+/// All points of a diagram, including those in its boundaries.
+fn points(diagram: &Diagram) -> Vec<Vec<SliceIndex>> {
+    let Diagram::DiagramN(diagram) = diagram else {
+        return vec![vec![]];
+    };
+    SliceIndex::for_size(diagram.size())
+        .flat_map(|index| {
+            points(&diagram.slice(index).unwrap())
+                .into_iter()
+                .map(move |mut point| {
+                    point.insert(0, index);
+                    point
+                })
+        })
+        .collect()
+}
+
+fn slice(diagram: &Diagram, point: &[SliceIndex]) -> Option<Diagram> {
+    point.iter().try_fold(diagram.clone(), |slice, index| {
+        DiagramN::try_from(slice).ok()?.slice(*index)
+    })
+}
+
+// every point of a regular slice is sent to a point of the adjacent slices
+#[test_case(examples::associator())]
+#[test_case(examples::two_monoid())]
+#[test_case(examples::crossing())]
+#[test_case(examples::three_beads())]
+#[test_case(examples::snake())]
+#[test_case(examples::lips())]
+#[test_case(examples::pants_unit())]
+#[test_case(examples::two_cups())]
+fn valid((_sig, diagram): (impl Signature, DiagramN)) {
+    use Height::{Regular, Singular};
+
+    let slices: Vec<_> = diagram.slices().collect();
+    for (i, cospan) in diagram.cospans().iter().enumerate() {
+        let (source, singular, target) = (
+            &slices[usize::from(Regular(i))],
+            &slices[usize::from(Singular(i))],
+            &slices[usize::from(Regular(i + 1))],
+        );
+
+        for point in points(source) {
+            assert!(slice(singular, &image(&cospan.forward, &point)).is_some());
+            assert!(slice(target, &across(cospan, Direction::Forward, &point)).is_some());
+        }
+
+        for point in points(target) {
+            assert!(slice(singular, &image(&cospan.backward, &point)).is_some());
+            assert!(slice(source, &across(cospan, Direction::Backward, &point)).is_some());
+        }
+
+        for point in points(singular) {
+            assert!(slice(source, &preimage(&cospan.forward, &point)).is_some());
+        }
+    }
+}
+
+#[test]
+fn contraction() -> anyhow::Result<()> {
+    use Height::{Regular, Singular};
+
+    let (sig, diagram) = examples::two_scalars();
+    let contracted = diagram.clone().identity().contract(
+        Boundary::Target.into(),
+        &mut [],
+        0,
+        Direction::Forward,
+        Some(Bias::Lower),
+        &sig,
+    )?;
+    assert_eq!(contracted.target().dimension(), 2);
+
+    let track = |height: Height| source_to_target(&contracted, &[height.into()]);
+    assert_eq!(track(Regular(0)), vec![Regular(0).into()]);
+    assert_eq!(track(Singular(0)), vec![Singular(0).into()]);
+    assert_eq!(track(Regular(1)), vec![Singular(0).into()]);
+    assert_eq!(track(Singular(1)), vec![Singular(0).into()]);
+    assert_eq!(track(Regular(2)), vec![Regular(1).into()]);
+    assert_eq!(
+        source_to_target(&contracted, &[Boundary::Target.into()]),
+        vec![Boundary::Target.into()]
+    );
+
+    Ok(())
+}
+
+// slices away from the attachment are where they were before
+#[test]
+fn attach_source() -> anyhow::Result<()> {
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    let ff = f.attach(&f, Boundary::Target, &[])?;
+    let m = sig.add(ff, f)?;
+    let attached = m.attach(&m, Boundary::Source, &[0])?;
+
+    let (before, after) = (Diagram::from(m.clone()), Diagram::from(attached));
+    for point in points(&before) {
+        let tracked = attachment(&m, Boundary::Source.into(), m.size(), &point);
+        assert_eq!(slice(&after, &tracked), slice(&before, &point));
+    }
+    Ok(())
+}
+
+#[test]
+fn attach_target() -> anyhow::Result<()> {
+    use Height::Regular;
+
+    let mut sig = SignatureBuilder::default();
+    let x = sig.add_zero();
+    let f = sig.add(x, x)?;
+    let ff = f.attach(&f, Boundary::Target, &[])?;
+    let m = sig.add(ff, f.clone())?;
+
+    let path = BoundaryPath(Boundary::Target, 1);
+    let tracked = attachment(&m, path, 1, &[Regular(0).into(), Boundary::Target.into()]);
+    assert_eq!(tracked, vec![Regular(0).into(), Regular(2).into()]);
+    let tracked = attachment(
+        &m,
+        path,
+        1,
+        &[Boundary::Target.into(), Boundary::Target.into()],
+    );
+    assert_eq!(tracked, vec![Boundary::Target.into(), Regular(1).into()]);
+    Ok(())
+}
@@ -1,12 +1,23 @@
-use std::convert::{Into, TryFrom};
+use std::{
+    convert::{Into, TryFrom},
+    ops::RangeInclusive,
+};
 
 use homotopy::Homotopy;
+use homotopy_common::hash::FastHashMap;
 use homotopy_core::{
     common::{Boundary, BoundaryPath, Direction, Generator, Height, Mode, SliceIndex},
     contraction::ContractionError,
+    crop::CropError,
+    desuspension::{Desuspension, DesuspensionError},
     diagram::{AttachmentError, NewDiagramError},
+    excision::ExcisionError,
     expansion::ExpansionError,
-    signature::Signature as S,
+    knot::{knot, Crossing, KnotError},
+    morphism::{MorphismError, SignatureMorphism},
+    signature::{dual, GeneratorInfo as _, GeneratorReport, Signature as S},
+    tracking,
+    typecheck::typecheck,
     Diagram, Diagram0, DiagramN, Orientation,
 };
 use im::Vector;
@@ -64,6 +75,35 @@ impl Workspace {
     pub fn visible_dimension(&self) -> usize {
         self.diagram.dimension() - self.path.len()
     }
+
+    /// Move the path to follow the slice it points at from the source to the target of a
+    /// diagram, which the workspace diagram has just been replaced with.
+    fn track_path(&mut self, diagram: &DiagramN) {
+        let path: Vec<_> = self.path.iter().copied().collect();
+        self.path = tracking::source_to_target(diagram, &path).into();
+    }
+}
+
+/// Follow a slice of the diagram visible at a path from the source to the target of a diagram,
+/// in the same way as [`Workspace::track_path`].
+fn track_slice(diagram: &DiagramN, path: &Vector<SliceIndex>, slice: SliceIndex) -> SliceIndex {
+    let point: Vec<_> = path.iter().copied().chain([slice]).collect();
+    tracking::source_to_target(diagram, &point)[path.len()]
+}
+
+/// Follow a slice of the diagram visible at a path through a homotopy on a boundary of a diagram,
+/// which attaches one cospan to it. A slice inside that boundary keeps showing the new boundary.
+fn track_boundary_slice(
+    diagram: &DiagramN,
+    boundary_path: BoundaryPath,
+    path: &Vector<SliceIndex>,
+    slice: SliceIndex,
+) -> SliceIndex {
+    let point: Vec<_> = path.iter().copied().chain([slice]).collect();
+    if point.get(boundary_path.depth()) == Some(&boundary_path.boundary().into()) {
+        return slice;
+    }
+    tracking::attachment(diagram, boundary_path, 1, &point)[path.len()]
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -123,26 +163,55 @@ pub enum Action {
 
     Attach(AttachOption),
 
+    /// Attach a diagram to the target of the diagram in the workspace at every position where it
+    /// fits without overlapping another, in a single step.
+    AttachEverywhere(DiagramN),
+
     Homotopy(Homotopy),
 
     Behead,
 
     Befoot,
 
+    /// Remove the cell at a point of the diagram in the workspace, given relative to the currently
+    /// visible slice.
+    Excise(Vec<Height>),
+
     Invert,
 
     Restrict,
 
+    /// Cut out the subdiagram of the visible slice lying inside a box, given by a range of heights
+    /// in each of its dimensions.
+    Crop(Vec<RangeInclusive<Height>>),
+
     Theorem,
 
     SuspendSignature,
 
     Suspend(Generator, Generator),
 
+    /// Undo [Action::Suspend] by removing the base generators and lowering every other generator
+    /// by one dimension, along with the diagrams in the workspace, the boundary and the stash.
+    Desuspend(Generator, Generator),
+
+    /// Replace the signature by its dual in a dimension, in which every cell of at least that
+    /// dimension is reversed in it, along with the diagrams in the workspace, the boundary and
+    /// the stash.
+    Dual(usize),
+
     Merge(Generator, Generator),
 
+    /// Substitute each generator by a diagram of the same type throughout the workspace, the
+    /// boundary and the stash. Generators whose boundaries change are retyped in the signature.
+    Substitute(Vec<(Generator, Diagram)>),
+
     ImportProof(SerializedData),
 
+    /// Replace the workspace by the 4-dimensional knot or link with a given PD code, whose strands
+    /// are copies of a given invertible 3-cell between identities, such as a suspended scalar.
+    ImportKnot(Generator, Vec<Crossing>),
+
     EditSignature(SignatureEdit),
 
     EditMetadata(MetadataEdit),
@@ -208,6 +277,7 @@ impl Action {
             Self::Attach(option) => proof.workspace.as_ref().map_or(false, |ws| {
                 option.boundary_path.is_none() || ws.diagram.dimension() > 0
             }),
+            Self::AttachEverywhere(_) => proof.workspace.is_some(),
             Self::Homotopy(_) => proof
                 .workspace
                 .as_ref()
@@ -225,6 +295,10 @@ impl Action {
                         }
                     })
             }
+            Self::Excise(_) => proof.workspace.as_ref().is_some_and(|ws| {
+                ws.diagram.dimension() > 0
+                    && ws.path.iter().all(|index| matches!(index, Interior(_)))
+            }),
             Self::Invert => proof
                 .workspace
                 .as_ref()
@@ -236,13 +310,25 @@ impl Action {
                         .iter()
                         .all(|index| !matches!(index, Interior(Singular(_))))
             }),
+            Self::Crop(bounds) => proof.workspace.as_ref().is_some_and(|ws| {
+                !bounds.is_empty()
+                    && bounds.len() <= ws.visible_dimension()
+                    && ws
+                        .path
+                        .iter()
+                        .all(|index| !matches!(index, Interior(Singular(_))))
+            }),
             Self::Theorem => proof
                 .workspace
                 .as_ref()
                 .map_or(false, |ws| ws.diagram.dimension() > 0),
-            Self::Suspend(_, _) | Self::SuspendSignature => proof.signature.has_generators(),
+            Self::Suspend(_, _) | Self::SuspendSignature | Self::Desuspend(_, _) => {
+                proof.signature.has_generators()
+            }
+            Self::Dual(k) => *k > 0 && proof.signature.has_generators(),
             Self::Merge(_, _) => true,
-            Self::ImportProof(_) => true,
+            Self::Substitute(images) => !images.is_empty(),
+            Self::ImportProof(_) | Self::ImportKnot(..) => true,
             Self::EditSignature(_) | Self::EditMetadata(_) => true, /* technically the edits could be trivial but do not worry about that for now */
             Self::FlipBoundary | Self::RecoverBoundary => proof.boundary.is_some(),
             Self::Stash => proof.workspace.is_some(),
@@ -268,10 +354,22 @@ pub enum ProofError {
     ExpansionError(#[from] ExpansionError),
     #[error(transparent)]
     ContractionError(#[from] ContractionError),
+    #[error(transparent)]
+    ExcisionError(#[from] ExcisionError),
+    #[error(transparent)]
+    CropError(#[from] CropError),
+    #[error(transparent)]
+    MorphismError(#[from] MorphismError),
     #[error("import failed")]
     Import,
+    #[error("import failed: {:?} is invalid: {:?}", .0.generator, .0.errors)]
+    InvalidGenerator(GeneratorReport),
     #[error(transparent)]
     SignatureError(#[from] SignatureError),
+    #[error(transparent)]
+    KnotError(#[from] KnotError),
+    #[error(transparent)]
+    DesuspensionError(#[from] DesuspensionError),
 }
 
 impl ProofState {
@@ -292,16 +390,24 @@ impl ProofState {
             Action::IncreaseView(count) => self.increase_view(*count),
             Action::DecreaseView(count) => self.decrease_view(*count),
             Action::Attach(option) => self.attach(option)?,
-            Action::Homotopy(Homotopy::Expand(homotopy)) => self.homotopy_expand(homotopy)?,
-            Action::Homotopy(Homotopy::Contract(homotopy)) => self.homotopy_contract(homotopy)?,
+            Action::AttachEverywhere(diagram) => self.attach_everywhere(diagram)?,
+            Action::Homotopy(Homotopy::Expand(homotopy)) => self.homotopy_expand(homotopy, None)?,
+            Action::Homotopy(Homotopy::Contract(homotopy)) => {
+                self.homotopy_contract(homotopy, None)?
+            }
             Action::Behead => self.behead(),
             Action::Befoot => self.befoot(),
+            Action::Excise(point) => self.excise(point)?,
             Action::Invert => self.invert()?,
             Action::Restrict => self.restrict(),
+            Action::Crop(bounds) => self.crop(bounds)?,
             Action::Theorem => self.theorem()?,
             Action::SuspendSignature => self.suspend_signature(),
             Action::Suspend(s, t) => self.suspend(*s, *t),
+            Action::Desuspend(s, t) => self.desuspend(*s, *t)?,
+            Action::Dual(k) => self.dual(*k),
             Action::Merge(from, to) => self.merge(*from, *to)?,
+            Action::Substitute(images) => self.substitute(images)?,
             Action::EditSignature(edit) => self.edit_signature(edit)?,
             Action::FlipBoundary => self.flip_boundary(),
             Action::RecoverBoundary => self.recover_boundary(),
@@ -310,12 +416,44 @@ impl ProofState {
             Action::StashPop => self.stash_pop(),
             Action::StashApply => self.stash_apply(),
             Action::ImportProof(data) => self.import_proof(data)?,
+            Action::ImportKnot(strand, code) => self.import_knot(*strand, code)?,
             Action::EditMetadata(edit) => self.edit_metadata(edit),
             Action::Nothing => false,
         };
         Ok(result)
     }
 
+    /// Update the state in response to an [Action], following a slice of the visible diagram
+    /// into the result in the same way as the workspace path.
+    ///
+    /// Only attachments and homotopies move the slice; it is dropped by every other action.
+    /// Returns a boolean indicating if the state was updated, together with the followed slice.
+    pub fn update_tracking(
+        &mut self,
+        action: &Action,
+        mut slice: SliceIndex,
+    ) -> Result<(bool, Option<SliceIndex>), ProofError> {
+        match action {
+            Action::Attach(_) | Action::AttachEverywhere(_) => {
+                // Attachments do not depend on the path, so the slice is followed as part of it.
+                let Some(ws) = &mut self.workspace else { return Ok((false, None)) };
+                ws.path.push_back(slice);
+                let result = self.update(action);
+                let slice = self.workspace.as_mut().and_then(|ws| ws.path.pop_back());
+                Ok((result?, slice))
+            }
+            Action::Homotopy(Homotopy::Expand(homotopy)) => Ok((
+                self.homotopy_expand(homotopy, Some(&mut slice))?,
+                Some(slice),
+            )),
+            Action::Homotopy(Homotopy::Contract(homotopy)) => Ok((
+                self.homotopy_contract(homotopy, Some(&mut slice))?,
+                Some(slice),
+            )),
+            _ => Ok((self.update(action)?, None)),
+        }
+    }
+
     /// Determines if a given [Action] should reset the panzoom state, given the current  [ProofState].
     pub fn resets_panzoom(&self, action: &Action) -> bool {
         match *action {
@@ -334,6 +472,48 @@ impl ProofState {
         }
     }
 
+    /// All well-typed variants of a contraction, one for each bias that produces a distinct
+    /// result, so that an ambiguous contraction can be offered as a choice of options.
+    pub fn contraction_options(&self, homotopy: &Contract) -> Vec<Contract> {
+        let Some(ws) = &self.workspace else { return vec![] };
+
+        let location = {
+            let mut location: Vec<_> = ws.path.iter().copied().collect();
+            location.extend(homotopy.location.clone());
+            location
+        };
+
+        let (boundary_path, interior_path) = BoundaryPath::split(&location);
+
+        let options = if let Some(boundary_path) = boundary_path {
+            let Diagram::DiagramN(diagram) = &ws.diagram else { return vec![] };
+            diagram.contract_all(
+                boundary_path,
+                &interior_path,
+                homotopy.height,
+                homotopy.direction,
+                &self.signature,
+            )
+        } else {
+            ws.diagram.clone().identity().contract_all(
+                Boundary::Target.into(),
+                &interior_path,
+                homotopy.height,
+                homotopy.direction,
+                &self.signature,
+            )
+        };
+
+        options
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(bias, _)| Contract {
+                bias,
+                ..homotopy.clone()
+            })
+            .collect()
+    }
+
     /// Handler for [Action::CreateGeneratorZero].
     fn create_generator_zero(&mut self) -> bool {
         self.signature.create_generator_zero("Cell");
@@ -512,24 +692,53 @@ impl ProofState {
 
         let embedding: Vec<_> = option.embedding.iter().copied().collect();
 
-        if let Some(bp) = &option.boundary_path {
+        if let Some(bp) = option.boundary_path {
             let Diagram::DiagramN(diagram) = diagram else { return Ok(false) };
-            *diagram = diagram.attach(&option.diagram, bp.boundary(), &embedding)?;
+            let attached = diagram.attach(&option.diagram, bp.boundary(), &embedding)?;
+            // A path into the boundary that was attached to keeps showing the new boundary.
+            let path: Vec<_> = ws.path.iter().copied().collect();
+            if path.get(bp.depth()) != Some(&bp.boundary().into()) {
+                ws.path = tracking::attachment(diagram, bp, option.diagram.size(), &path).into();
+            }
+            *diagram = attached;
         } else {
-            *diagram = diagram
-                .clone()
-                .identity()
-                .attach(&option.diagram, Boundary::Target, &embedding)?
-                .target();
+            let attached =
+                diagram
+                    .clone()
+                    .identity()
+                    .attach(&option.diagram, Boundary::Target, &embedding)?;
+            *diagram = attached.target();
+            ws.track_path(&attached);
         }
 
         Ok(true)
     }
 
+    /// Handler for [Action::AttachEverywhere].
+    ///
+    /// Invalid if the workspace is empty.
+    fn attach_everywhere(&mut self, diagram: &DiagramN) -> Result<bool, ProofError> {
+        let Some(ws) = &mut self.workspace else { return Ok(false) };
+
+        let attached = ws
+            .diagram
+            .clone()
+            .identity()
+            .attach_everywhere(diagram, Boundary::Target)?;
+        ws.diagram = attached.target();
+        ws.track_path(&attached);
+
+        Ok(true)
+    }
+
     /// Handler for [Action::Homotopy].
     ///
     /// Invalid if the workspace is empty or has dimension 0.
-    fn homotopy_expand(&mut self, homotopy: &Expand) -> Result<bool, ProofError> {
+    fn homotopy_expand(
+        &mut self,
+        homotopy: &Expand,
+        slice: Option<&mut SliceIndex>,
+    ) -> Result<bool, ProofError> {
         let Some(ws) = &mut self.workspace else { return Ok(false) };
         let diagram = &mut ws.diagram;
 
@@ -543,25 +752,29 @@ impl ProofState {
 
         if let Some(boundary_path) = boundary_path {
             let Diagram::DiagramN(diagram) = diagram else { return Ok(false) };
-            *diagram = diagram.expand(
+            let expanded = diagram.expand(
                 boundary_path,
                 &mut interior_path,
                 homotopy.point,
                 homotopy.direction,
                 &self.signature,
             )?;
+            if let Some(slice) = slice {
+                *slice = track_boundary_slice(diagram, boundary_path, &ws.path, *slice);
+            }
+            *diagram = expanded;
         } else {
-            *diagram = diagram
-                .clone()
-                .identity()
-                .expand(
-                    Boundary::Target.into(),
-                    &mut interior_path,
-                    homotopy.point,
-                    homotopy.direction,
-                    &self.signature,
-                )?
-                .target();
+            let expanded = diagram.clone().identity().expand(
+                Boundary::Target.into(),
+                &mut interior_path,
+                homotopy.point,
+                homotopy.direction,
+                &self.signature,
+            )?;
+            if let Some(slice) = slice {
+                *slice = track_slice(&expanded, &ws.path, *slice);
+            }
+            ws.diagram = expanded.target();
         }
 
         let offset = boundary_path.map_or(0, |bp| bp.depth() + 1);
@@ -575,7 +788,11 @@ impl ProofState {
     /// Handler for [Action::Homotopy].
     ///
     /// Invalid if the workspace is empty or has dimension 0.
-    fn homotopy_contract(&mut self, homotopy: &Contract) -> Result<bool, ProofError> {
+    fn homotopy_contract(
+        &mut self,
+        homotopy: &Contract,
+        slice: Option<&mut SliceIndex>,
+    ) -> Result<bool, ProofError> {
         let Some(ws) = &mut self.workspace else { return Ok(false) };
         let diagram = &mut ws.diagram;
 
@@ -589,7 +806,7 @@ impl ProofState {
 
         if let Some(boundary_path) = boundary_path {
             let Diagram::DiagramN(diagram) = diagram else { return Ok(false) };
-            *diagram = diagram.contract(
+            let contracted = diagram.contract(
                 boundary_path,
                 &mut interior_path,
                 homotopy.height,
@@ -597,19 +814,23 @@ impl ProofState {
                 homotopy.bias,
                 &self.signature,
             )?;
+            if let Some(slice) = slice {
+                *slice = track_boundary_slice(diagram, boundary_path, &ws.path, *slice);
+            }
+            *diagram = contracted;
         } else {
-            *diagram = diagram
-                .clone()
-                .identity()
-                .contract(
-                    Boundary::Target.into(),
-                    &mut interior_path,
-                    homotopy.height,
-                    homotopy.direction,
-                    homotopy.bias,
-                    &self.signature,
-                )?
-                .target();
+            let contracted = diagram.clone().identity().contract(
+                Boundary::Target.into(),
+                &mut interior_path,
+                homotopy.height,
+                homotopy.direction,
+                homotopy.bias,
+                &self.signature,
+            )?;
+            if let Some(slice) = slice {
+                *slice = track_slice(&contracted, &ws.path, *slice);
+            }
+            ws.diagram = contracted.target();
         }
 
         let offset = boundary_path.map_or(0, |bp| bp.depth() + 1);
@@ -680,6 +901,28 @@ impl ProofState {
         true
     }
 
+    /// Handler for [Action::Excise].
+    ///
+    /// Invalid if the workspace is empty or has dimension 0, or if the path is not interior.
+    ///
+    /// Returns an error if there is no cell at the point or it cannot be excised.
+    fn excise(&mut self, point: &[Height]) -> Result<bool, ProofError> {
+        let Some(ws) = &mut self.workspace else { return Ok(false) };
+        let Diagram::DiagramN(diagram) = &ws.diagram else { return Ok(false) };
+
+        let mut location = vec![];
+        for index in &ws.path {
+            let SliceIndex::Interior(height) = index else { return Ok(false) };
+            location.push(*height);
+        }
+        location.extend(point);
+
+        ws.diagram = diagram.excise(&location, &self.signature)?.into();
+        ws.path.clear();
+
+        Ok(true)
+    }
+
     /// Handler for [Action::Invert].
     ///
     /// Invalid if the workspace is empty or has dimension 0.
@@ -723,6 +966,31 @@ impl ProofState {
         true
     }
 
+    /// Handler for [Action::Crop].
+    ///
+    /// Invalid if the workspace is empty, if the path contains a singular slice, or if the visible
+    /// slice has dimension 0.
+    ///
+    /// Returns an error if the box does not fit the visible slice.
+    fn crop(&mut self, bounds: &[RangeInclusive<Height>]) -> Result<bool, ProofError> {
+        let Some(ws) = &mut self.workspace else { return Ok(false) };
+
+        if ws
+            .path
+            .iter()
+            .any(|index| matches!(index, SliceIndex::Interior(Height::Singular(_))))
+        {
+            return Ok(false);
+        }
+
+        let Diagram::DiagramN(diagram) = ws.visible_diagram() else { return Ok(false) };
+
+        ws.diagram = diagram.crop(bounds)?.into();
+        ws.path = <_>::default();
+
+        Ok(true)
+    }
+
     /// Handler for [Action::Theorem].
     ///
     /// Invalid if the workspace is empty or has dimension 0.
@@ -788,6 +1056,86 @@ impl ProofState {
         true
     }
 
+    /// Handler for [Action::Desuspend].
+    ///
+    /// Returns an error if something is not suspended over the base generators, in which case
+    /// nothing is changed.
+    fn desuspend(&mut self, source: Generator, target: Generator) -> Result<bool, ProofError> {
+        let desuspension = Desuspension::new(source, target);
+
+        let generators: FastHashMap<usize, (Generator, Diagram)> = desuspension
+            .apply_signature(&self.signature)?
+            .into_iter()
+            .map(|(generator, diagram)| (generator.id, (generator, diagram)))
+            .collect();
+        let workspace = self
+            .workspace
+            .as_ref()
+            .map(|ws| desuspension.apply(&ws.diagram))
+            .transpose()?;
+        let boundary = self
+            .boundary
+            .as_ref()
+            .map(|bd| desuspension.apply(&bd.diagram))
+            .transpose()?;
+        let stash = self
+            .stash
+            .iter()
+            .map(|ws| desuspension.apply(&ws.diagram))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.signature = self.signature.filter_map(|info| {
+            let (generator, diagram) = generators.get(&info.generator.id)?;
+            Some(GeneratorInfo {
+                generator: *generator,
+                diagram: diagram.clone(),
+                ..info.clone()
+            })
+        });
+
+        let lower = |ws: &mut Workspace, diagram: Diagram| {
+            ws.view.dimension = ws.view.dimension.min(diagram.dimension() as u8);
+            ws.diagram = diagram;
+            ws.path.clear();
+        };
+        if let (Some(ws), Some(diagram)) = (&mut self.workspace, workspace) {
+            lower(ws, diagram);
+        }
+        if let (Some(bd), Some(diagram)) = (&mut self.boundary, boundary) {
+            bd.diagram = diagram;
+        }
+        for (ws, diagram) in self.stash.iter_mut().zip(stash) {
+            lower(ws, diagram);
+        }
+
+        Ok(true)
+    }
+
+    /// Handler for [Action::Dual].
+    fn dual(&mut self, k: usize) -> bool {
+        let dual = dual(&self.signature, k);
+        self.signature = self.signature.filter_map(|info| {
+            Some(GeneratorInfo {
+                diagram: dual.generator_info(info.generator)?.diagram().clone(),
+                ..info.clone()
+            })
+        });
+
+        if let Some(ws) = &mut self.workspace {
+            ws.diagram = ws.diagram.reverse_in(k);
+            ws.path.clear();
+        }
+        if let Some(bd) = &mut self.boundary {
+            bd.diagram = bd.diagram.reverse_in(k);
+        }
+        for ws in self.stash.iter_mut() {
+            ws.diagram = ws.diagram.reverse_in(k);
+            ws.path.clear();
+        }
+
+        true
+    }
+
     /// Handler for [Action::Merge].
     fn merge(&mut self, from: Generator, to: Generator) -> Result<bool, ProofError> {
         let info_from = self
@@ -831,21 +1179,77 @@ impl ProofState {
 
         Ok(true)
     }
+
+    /// Handler for [Action::Substitute].
+    ///
+    /// The signature is replaced by its image, in which the generators which are not substituted
+    /// go between the images of their boundaries. Returns an error if an image is ill-typed or
+    /// cannot be substituted somewhere, in which case nothing is changed.
+    fn substitute(&mut self, images: &[(Generator, Diagram)]) -> Result<bool, ProofError> {
+        let morphism =
+            SignatureMorphism::new(&self.signature, &self.signature, images.iter().cloned())?;
+        let target = morphism.apply_signature();
+
+        let workspace = self
+            .workspace
+            .as_ref()
+            .map(|ws| morphism.apply(&ws.diagram))
+            .transpose()?;
+        let boundary = self
+            .boundary
+            .as_ref()
+            .map(|bd| morphism.apply(&bd.diagram))
+            .transpose()?;
+        let stash = self
+            .stash
+            .iter()
+            .map(|ws| morphism.apply(&ws.diagram))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.signature = self.signature.filter_map(|info| {
+            Some(GeneratorInfo {
+                diagram: target.generator_info(info.generator)?.diagram().clone(),
+                ..info.clone()
+            })
+        });
+        if let (Some(ws), Some(diagram)) = (&mut self.workspace, workspace) {
+            if ws.diagram != diagram {
+                ws.diagram = diagram;
+                ws.path.clear();
+            }
+        }
+        if let (Some(bd), Some(diagram)) = (&mut self.boundary, boundary) {
+            bd.diagram = diagram;
+        }
+        for (ws, diagram) in self.stash.iter_mut().zip(stash) {
+            if ws.diagram != diagram {
+                ws.diagram = diagram;
+                ws.path.clear();
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Handler for [Action::ImportProof].
     fn import_proof(&mut self, data: &SerializedData) -> Result<bool, ProofError> {
         let ((signature, workspace), metadata) = serialize::deserialize(&data.0)
             .or_else(|| migration::deserialize(&data.0))
             .ok_or(ProofError::Import)?;
-        for info in signature.iter() {
-            info.diagram
-                .check(Mode::Deep)
-                .map_err(|_err| ProofError::Import)?;
+        if let Some(report) = signature
+            .validate()
+            .into_iter()
+            .find(|report| !report.is_valid())
+        {
+            return Err(ProofError::InvalidGenerator(report));
         }
         if let Some(workspace) = workspace.as_ref() {
             workspace
                 .diagram
                 .check(Mode::Deep)
                 .map_err(|_err| ProofError::Import)?;
+            typecheck(&workspace.diagram, &signature, Mode::Deep)
+                .map_err(|_err| ProofError::Import)?;
         }
         self.signature = signature;
         self.workspace = workspace;
@@ -855,6 +1259,23 @@ impl ProofState {
         Ok(true)
     }
 
+    /// Handler for [Action::ImportKnot].
+    fn import_knot(&mut self, strand: Generator, code: &[Crossing]) -> Result<bool, ProofError> {
+        let info = self
+            .signature
+            .generator_info(strand)
+            .ok_or(ProofError::UnknownGeneratorSelected)?;
+        let strand: DiagramN = info
+            .diagram
+            .clone()
+            .try_into()
+            .map_err(|_err| KnotError::Strand)?;
+        let knot = knot(&strand, code, &self.signature)?;
+
+        self.workspace = Some(Workspace::new(knot.into()));
+        Ok(true)
+    }
+
     /// Handler for [Action::EditSignature].
     fn edit_signature(&mut self, edit: &SignatureEdit) -> Result<bool, ProofError> {
         // intercept remove events in order to clean-up workspace and boundaries
@@ -1,6 +1,7 @@
 use homotopy_core::{
     common::{Direction, Height, SingularHeight, SliceIndex},
     contraction::Bias,
+    search::Move,
 };
 use serde::{Deserialize, Serialize};
 
@@ -24,3 +25,30 @@ pub enum Homotopy {
     Contract(Contract),
     Expand(Expand),
 }
+
+impl From<Move> for Homotopy {
+    fn from(m: Move) -> Self {
+        match m {
+            Move::Contract {
+                location,
+                height,
+                direction,
+                bias,
+            } => Self::Contract(Contract {
+                height,
+                direction,
+                bias,
+                location: location.into_iter().map(SliceIndex::Interior).collect(),
+            }),
+            Move::Expand {
+                location,
+                point,
+                direction,
+            } => Self::Expand(Expand {
+                point,
+                direction,
+                location: location.into_iter().map(SliceIndex::Interior).collect(),
+            }),
+        }
+    }
+}
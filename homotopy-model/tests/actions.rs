@@ -1,4 +1,17 @@
-pub use homotopy_model::{history::Proof, proof::Action, serialize};
+use homotopy_core::{
+    common::{BoundaryPath, Mode},
+    signature::Signature as _,
+    typecheck::typecheck,
+    Boundary, Diagram0, DiagramN, Direction, Generator, Height, SliceIndex,
+};
+pub use homotopy_model::{
+    history::Proof,
+    proof::{
+        homotopy::{Contract, Homotopy},
+        Action, AttachOption, ProofError, ProofState, SerializedData, Workspace,
+    },
+    serialize,
+};
 
 fn actions_test_helper(json: &str) -> Proof {
     let (_safe, actions): (bool, Vec<Action>) = serde_json::from_str(json).unwrap();
@@ -13,6 +26,20 @@ fn actions_test_helper(json: &str) -> Proof {
     proof
 }
 
+/// Replay a dump of actions into a [ProofState], without recording any history.
+fn replay(json: &str) -> ProofState {
+    let (_safe, actions): (bool, Vec<Action>) = serde_json::from_str(json).unwrap();
+    let mut proof = ProofState::default();
+
+    for a in &actions {
+        proof
+            .update(a)
+            .expect("Actions should replay without errors.");
+    }
+
+    proof
+}
+
 #[test]
 fn construct_associator() {
     let action_dump = include_str!("examples/associator.json");
@@ -47,3 +74,219 @@ fn merge_contract_r1() {
 
     let _proof = actions_test_helper(action_dump);
 }
+
+#[test]
+fn suspend_and_desuspend() {
+    let mut proof = replay(include_str!("examples/associator.json"));
+    let original = proof.clone();
+
+    proof.update(&Action::SuspendSignature).unwrap();
+    let base = |name: &str| {
+        proof
+            .signature
+            .iter()
+            .find(|info| info.name.starts_with(name))
+            .unwrap()
+            .generator
+    };
+    let (source, target) = (base("Base Source"), base("Base Target"));
+    assert!(proof.update(&Action::Desuspend(target, source)).is_err());
+    proof.update(&Action::Desuspend(source, target)).unwrap();
+
+    let diagrams = |proof: &ProofState| {
+        proof
+            .signature
+            .iter()
+            .map(|info| (info.generator, info.diagram.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(diagrams(&proof), diagrams(&original));
+    assert_eq!(
+        proof.workspace.map(|ws| ws.diagram),
+        original.workspace.map(|ws| ws.diagram)
+    );
+}
+
+#[test]
+fn dual() {
+    let mut proof = replay(include_str!("examples/associator.json"));
+    let original = proof.clone();
+
+    for k in 1..=3 {
+        proof.update(&Action::Dual(k)).unwrap();
+        let diagram = proof.workspace.as_ref().unwrap().diagram.clone();
+        assert_ne!(diagram, original.workspace.as_ref().unwrap().diagram);
+        typecheck(&diagram, &proof.signature, Mode::Deep).unwrap();
+
+        proof.update(&Action::Dual(k)).unwrap();
+        assert_eq!(proof, original);
+    }
+}
+
+/// A proof with a wire `f` and a generator `m` merging two wires into one, viewing three wires.
+fn merging_wires() -> (ProofState, DiagramN, DiagramN) {
+    let mut proof = ProofState::default();
+    let x = proof.signature.create_generator_zero("x");
+    let f = proof
+        .signature
+        .create_generator(x.into(), x.into(), "f", false)
+        .unwrap();
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let fff = ff.attach(&f, Boundary::Target, &[]).unwrap();
+    let m = proof
+        .signature
+        .create_generator(ff.into(), f.clone().into(), "m", false)
+        .unwrap();
+    proof.workspace = Some(Workspace::new(fff.into()));
+
+    (proof, f, m)
+}
+
+fn attach_option(diagram: DiagramN, embedding: &[usize]) -> AttachOption {
+    AttachOption {
+        generator: diagram.max_generator().generator,
+        boundary_path: None,
+        embedding: embedding.iter().copied().collect(),
+        tag: None,
+        diagram,
+    }
+}
+
+// the viewed slice stays on the same wire when the wires below it are merged
+#[test]
+fn attach_tracks_path() {
+    let (mut proof, _f, m) = merging_wires();
+    let ws = proof.workspace.as_mut().unwrap();
+    ws.path.push_back(Height::Singular(2).into());
+
+    let option = attach_option(m, &[0]);
+    assert!(proof.update(&Action::Attach(option)).unwrap());
+
+    let path: Vec<SliceIndex> = proof.workspace.unwrap().path.into_iter().collect();
+    assert_eq!(path, vec![Height::Singular(1).into()]);
+}
+
+#[test]
+fn boundary_attach_tracks_path() {
+    let (mut proof, f, _m) = merging_wires();
+    let ws = proof.workspace.as_mut().unwrap();
+    ws.path.push_back(Height::Singular(0).into());
+
+    let option = AttachOption {
+        boundary_path: Some(BoundaryPath(Boundary::Source, 0)),
+        ..attach_option(f, &[])
+    };
+    assert!(proof.update(&Action::Attach(option)).unwrap());
+
+    let workspace = proof.workspace.unwrap();
+    assert_eq!(workspace.diagram.dimension(), 1);
+    let path: Vec<SliceIndex> = workspace.path.into_iter().collect();
+    assert_eq!(path, vec![Height::Singular(1).into()]);
+}
+
+#[test]
+fn attach_tracks_slice() {
+    let (mut proof, _f, m) = merging_wires();
+
+    let option = attach_option(m, &[0]);
+    let (updated, slice) = proof
+        .update_tracking(&Action::Attach(option), Height::Singular(2).into())
+        .unwrap();
+    assert!(updated);
+    assert_eq!(slice, Some(Height::Singular(1).into()));
+    assert!(proof.workspace.unwrap().path.is_empty());
+}
+
+// merging two disjoint pairs of wires one after the other, the slice between the merges is sent
+// to the single merge of the contraction
+#[test]
+fn contract_tracks_slice() {
+    let (mut proof, f, m) = merging_wires();
+    let ws = proof.workspace.as_mut().unwrap();
+    let ffff = DiagramN::try_from(ws.diagram.clone())
+        .unwrap()
+        .attach(&f, Boundary::Target, &[])
+        .unwrap();
+    let merges = ffff
+        .identity()
+        .attach(&m, Boundary::Target, &[0])
+        .unwrap()
+        .attach(&m, Boundary::Target, &[1])
+        .unwrap();
+    *ws = Workspace::new(merges.into());
+
+    let contract = Contract {
+        height: 0,
+        direction: Direction::Forward,
+        bias: None,
+        location: vec![],
+    };
+    let (updated, slice) = proof
+        .update_tracking(
+            &Action::Homotopy(Homotopy::Contract(contract)),
+            Height::Regular(1).into(),
+        )
+        .unwrap();
+    assert!(updated);
+    assert_eq!(slice, Some(Height::Singular(0).into()));
+    let diagram = DiagramN::try_from(proof.workspace.unwrap().diagram).unwrap();
+    assert_eq!(diagram.size(), 1);
+}
+
+#[test]
+fn import_validates_signature() {
+    let proof = replay(include_str!("examples/associator.json"));
+    let data = serialize::serialize(
+        proof.signature.clone(),
+        proof.workspace.clone(),
+        proof.metadata.clone(),
+    );
+    assert!(ProofState::default()
+        .update(&Action::ImportProof(SerializedData(data)))
+        .unwrap());
+
+    // The target of f is a point that is missing from the signature.
+    let mut proof = ProofState::default();
+    let x = proof.signature.create_generator_zero("x");
+    let y = Diagram0::from(Generator::new(100, 0));
+    proof
+        .signature
+        .create_generator(x.into(), y.into(), "f", false)
+        .unwrap();
+    let data = serialize::serialize(proof.signature, None, proof.metadata);
+    assert!(matches!(
+        ProofState::default().update(&Action::ImportProof(SerializedData(data))),
+        Err(ProofError::InvalidGenerator(_))
+    ));
+}
+
+// substituting a wire by two copies of itself doubles the wire of a bead, which is retyped
+#[test]
+fn substitute_retypes() {
+    let mut proof = ProofState::default();
+    let x = proof.signature.create_generator_zero("x");
+    let f = proof
+        .signature
+        .create_generator(x.into(), x.into(), "f", false)
+        .unwrap();
+    let ff = f.attach(&f, Boundary::Target, &[]).unwrap();
+    let a = proof
+        .signature
+        .create_generator(f.clone().into(), f.clone().into(), "a", false)
+        .unwrap();
+    proof.workspace = Some(Workspace::new(a.clone().into()));
+
+    let images = vec![(f.max_generator().generator, ff.clone().into())];
+    assert!(proof.update(&Action::Substitute(images)).unwrap());
+
+    let diagram = proof.workspace.unwrap().diagram;
+    typecheck(&diagram, &proof.signature, Mode::Deep).unwrap();
+    let retyped = proof
+        .signature
+        .generator_info(a.max_generator().generator)
+        .unwrap()
+        .diagram
+        .clone();
+    assert_eq!(diagram, retyped);
+    assert_eq!(DiagramN::try_from(retyped).unwrap().source(), ff.into());
+}
@@ -0,0 +1,28 @@
+use homotopy_core::homology::{signature_homology, stratum_homology};
+use homotopy_model::serialize;
+
+#[test]
+fn torus() {
+    let ((signature, _), _) =
+        serialize::deserialize(include_bytes!("../../examples/torus.hom")).unwrap();
+    assert_eq!(
+        signature_homology(&signature).unwrap().betti_numbers,
+        vec![1, 2, 1]
+    );
+}
+
+#[test]
+fn knotted_sphere() {
+    let ((signature, workspace), _) =
+        serialize::deserialize(include_bytes!("../../examples/knotted_sphere.hom")).unwrap();
+    let diagram = workspace.unwrap().diagram;
+    let sphere = diagram.max_generator().generator;
+
+    let homology = stratum_homology(&diagram, sphere).unwrap();
+    assert_eq!(homology.betti_numbers, vec![1, 0, 1]);
+    assert_eq!(homology.euler_characteristic(), 2);
+    assert_eq!(
+        signature_homology(&signature).unwrap().betti_numbers,
+        vec![1, 0, 1]
+    );
+}
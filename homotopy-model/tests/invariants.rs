@@ -0,0 +1,104 @@
+use homotopy_core::{
+    invariants::Invariants,
+    knot::{self, KnotError},
+    DiagramN,
+};
+use homotopy_model::{
+    proof::{Action, ProofError, ProofState},
+    serialize,
+};
+
+fn invariants(data: &[u8]) -> Invariants {
+    let ((_, workspace), _) = serialize::deserialize(data).unwrap();
+    Invariants::new(&workspace.unwrap().diagram).unwrap()
+}
+
+fn assert_braid(data: &[u8], word: &[isize]) {
+    let invariants = invariants(data);
+    assert_eq!(invariants.braid_word(), Some(word));
+    assert_eq!(invariants.components(), 2);
+    assert_eq!(invariants.writhe(), word.len() as isize);
+    assert_eq!(invariants.linking_number(0, 1), word.len() as f64 / 2.0);
+}
+
+#[test]
+fn double_braid() {
+    assert_braid(include_bytes!("../../examples/double_braid.hom"), &[1, 1]);
+}
+
+#[test]
+fn triple_braid() {
+    assert_braid(
+        include_bytes!("../../examples/triple_braid.hom"),
+        &[1, 1, 1],
+    );
+}
+
+#[test]
+fn double_braid_cup_cap() {
+    assert_braid(
+        include_bytes!("../../examples/double_braid_cup_cap.hom"),
+        &[1, 1],
+    );
+}
+
+// the source of the isotopy is a curl, crossing itself once
+#[test]
+fn curl() {
+    let ((_, workspace), _) =
+        serialize::deserialize(include_bytes!("../../examples/figure_8_isotopy.hom")).unwrap();
+    let isotopy: DiagramN = workspace.unwrap().diagram.try_into().unwrap();
+    let invariants = Invariants::new(&isotopy.source()).unwrap();
+
+    let [crossing] = invariants.crossings() else {
+        panic!("expected a single crossing");
+    };
+    assert_eq!((crossing.over, crossing.under), (0, 0));
+    assert_eq!(invariants.components(), 1);
+    assert_eq!(invariants.writhe(), 1);
+    assert_eq!(invariants.braid_word(), None);
+}
+
+#[test]
+fn import_knot() {
+    let mut proof = ProofState::default();
+    let a = proof.signature.create_generator_zero("a");
+    let b = proof.signature.create_generator_zero("b");
+    let x = proof
+        .signature
+        .create_generator(a.into(), b.into(), "x", false)
+        .unwrap();
+    let strand = proof
+        .signature
+        .create_generator(x.clone().identity().into(), x.identity().into(), "s", true)
+        .unwrap();
+    let signature = proof.signature.clone();
+
+    let code = knot::parse_pd("X[1,5,2,4], X[3,1,4,6], X[5,3,6,2]").unwrap();
+    let action = Action::ImportKnot(strand.max_generator().generator, code);
+    assert!(proof.update(&action).unwrap());
+    assert_eq!(proof.signature, signature);
+
+    let diagram = proof.workspace.unwrap().diagram;
+    assert_eq!(diagram.dimension(), 4);
+    let invariants = Invariants::new(&diagram).unwrap();
+    assert_eq!(invariants.components(), 1);
+    assert_eq!(invariants.writhe(), 3);
+}
+
+#[test]
+fn import_knot_needs_strand() {
+    let mut proof = ProofState::default();
+    let x = proof.signature.create_generator_zero("x");
+    let scalar = proof
+        .signature
+        .create_generator(x.identity().into(), x.identity().into(), "s", true)
+        .unwrap();
+
+    let action = Action::ImportKnot(scalar.max_generator().generator, vec![]);
+    assert!(matches!(
+        proof.update(&action),
+        Err(ProofError::KnotError(KnotError::Strand))
+    ));
+    assert!(proof.workspace.is_none());
+}
@@ -4,7 +4,11 @@ use yew::prelude::*;
 use crate::{
     app::tex::TexSpan,
     model::{
-        proof::{self, AttachOption, Signature},
+        proof::{
+            self,
+            homotopy::{Contract, Homotopy},
+            AttachOption, Signature,
+        },
         Action, Selectables,
     },
 };
@@ -41,6 +45,9 @@ impl Component for AttachView {
             Selectables::Merge(from, tos) => html! {
                 { for tos.iter().map(|&to| Self::view_merge_option(ctx, *from, to)) }
             },
+            Selectables::Contract(options) => html! {
+                { for options.iter().map(|option| Self::view_contract_option(ctx, option)) }
+            },
         }
     }
 }
@@ -119,4 +126,24 @@ impl AttachView {
             </li>
         }
     }
+
+    pub fn view_contract_option(ctx: &Context<Self>, option: &Contract) -> Html {
+        let onclick = ctx.props().dispatch.reform({
+            let option = option.clone();
+            move |_| Action::Proof(proof::Action::Homotopy(Homotopy::Contract(option.clone())))
+        });
+
+        let name = option
+            .bias
+            .map_or_else(|| "Unbiased".to_owned(), |bias| format!("{bias:?} bias"));
+
+        html! {
+            <li
+                class="attach__option"
+                onclick={onclick}
+            >
+                <span class="attach__option-name">{name}</span>
+            </li>
+        }
+    }
 }
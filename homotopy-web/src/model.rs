@@ -1,12 +1,19 @@
+use std::rc::Rc;
+
 pub use history::Proof;
 use history::{History, UndoState};
 use homotopy_core::{
     common::{BoundaryPath, Generator},
+    contraction::ContractionError,
+    index::EmbeddingIndex,
     signature::Signature,
     Boundary, Diagram, DiagramN, SliceIndex,
 };
 use homotopy_graphics::{manim, stl, svg, tikz};
-use homotopy_model::proof::AttachOption;
+use homotopy_model::proof::{
+    homotopy::{Contract, Homotopy},
+    AttachOption,
+};
 pub use homotopy_model::{history, migration, proof, serialize};
 use serde::Serialize;
 use thiserror::Error;
@@ -70,6 +77,7 @@ impl From<history::Action> for Action {
 pub enum Selectables {
     Merge(Generator, Vec<Generator>),
     Attach(Vec<AttachOption>),
+    Contract(Vec<Contract>),
 }
 
 impl Selectables {
@@ -77,6 +85,7 @@ impl Selectables {
         match self {
             Self::Attach(_) => "Attach",
             Self::Merge(_, _) => "Merge",
+            Self::Contract(_) => "Contract",
         }
     }
 }
@@ -87,6 +96,20 @@ pub struct State {
     pub options: Option<Selectables>,
     pub attachment_highlight: Option<AttachOption>,
     pub slice_highlight: Option<SliceIndex>,
+    attach_index: Option<AttachIndex>,
+}
+
+/// The generator, tag and diagram of an attachment option.
+type AttachKey = (Generator, Option<String>, DiagramN);
+
+/// An [EmbeddingIndex] of attachment options, together with what it was built from.
+#[derive(Debug, Clone)]
+struct AttachIndex {
+    signature: proof::Signature,
+    dimension: usize,
+    boundary: Boundary,
+    weak_units: bool,
+    index: Rc<EmbeddingIndex<AttachKey>>,
 }
 
 impl State {
@@ -103,16 +126,45 @@ impl State {
                 // we risk funny business with circular action imports.
                 crate::panic::push_action(&action);
 
-                if self.history.try_redo(&action).is_err() {
+                let mut slice_highlight = None;
+                let previous = self
+                    .slice_highlight
+                    .map(|slice| (slice, self.proof().clone()));
+                if self.history.try_redo(&action).is_ok() {
+                    // The redone state is already known, so the action is only replayed to follow
+                    // the highlighted slice.
+                    if let Some((slice, mut proof)) = previous {
+                        slice_highlight = proof
+                            .update_tracking(&action, slice)
+                            .ok()
+                            .and_then(|(_, slice)| slice);
+                    }
+                } else {
                     let mut proof = self.proof().clone();
-                    let res = proof.update(&action);
+                    let res = match self.slice_highlight {
+                        Some(slice) => proof.update_tracking(&action, slice).map(|(res, slice)| {
+                            slice_highlight = slice;
+                            res
+                        }),
+                        None => proof.update(&action),
+                    };
                     if matches!(res, Err(_) | Ok(false)) {
                         crate::panic::pop_action();
+                        if matches!(
+                            res,
+                            Err(proof::ProofError::ContractionError(
+                                ContractionError::Ambiguous
+                            ))
+                        ) && self.contraction_options(&action)
+                        {
+                            return Ok(true);
+                        }
                         return Ok(res?);
                     }
                     self.history.add(action, proof);
                 }
                 self.clear_selections();
+                self.slice_highlight = slice_highlight;
             }
 
             Action::History(history::Action::Move(dir)) => {
@@ -260,6 +312,14 @@ impl State {
                         *from,
                         *tos.get(index).ok_or(ModelError::IndexOutOfBounds)?,
                     ),
+                    Some(Selectables::Contract(options)) => {
+                        proof::Action::Homotopy(Homotopy::Contract(
+                            options
+                                .get(index)
+                                .ok_or(ModelError::IndexOutOfBounds)?
+                                .clone(),
+                        ))
+                    }
                 };
                 self.update(Action::Proof(action))?;
             }
@@ -300,57 +360,100 @@ impl State {
 
         let boundary = boundary_path.map_or(Boundary::Target, BoundaryPath::boundary);
 
-        for info in self.proof().signature.iter() {
-            macro_rules! extend {
-                ($diagram:expr, $tag:expr) => {
-                    let needle = $diagram.slice(boundary.flip()).unwrap();
-                    matches.extend(
-                        haystack
-                            .embeddings(&needle)
-                            .filter(|embedding| needle.contains_point(&point, embedding))
-                            .map(|embedding| AttachOption {
-                                generator: info.generator,
-                                diagram: $diagram,
-                                tag: $tag,
-                                boundary_path,
-                                embedding: embedding.into_iter().collect(),
-                            }),
-                    );
-                };
+        let index = self.attach_index(haystack.dimension(), boundary, weak_units);
+        matches.extend(
+            index
+                .embeddings(&haystack)
+                .filter(|((_, _, diagram), embedding)| {
+                    let needle = diagram.slice(boundary.flip()).unwrap();
+                    needle.contains_point(&point, embedding)
+                })
+                .map(|((generator, tag, diagram), embedding)| AttachOption {
+                    generator: *generator,
+                    diagram: diagram.clone(),
+                    tag: tag.clone(),
+                    boundary_path,
+                    embedding: embedding.into_iter().collect(),
+                }),
+        );
+
+        match matches.len() {
+            0 => {
+                self.clear_selections();
+                return Err(ModelError::NoAttachment);
             }
+            1 => {
+                self.clear_selections();
+                self.update(Action::Proof(proof::Action::Attach(
+                    matches.into_iter().next().unwrap(),
+                )))?;
+            }
+            _ => {
+                self.options = Some(Selectables::Attach(matches));
+                self.attachment_highlight = None;
+                self.slice_highlight = None;
+            }
+        }
+
+        Ok(())
+    }
 
-            match info.generator.dimension.cmp(&(haystack.dimension() + 1)) {
+    /// The index of the diagrams that can be attached to a diagram of the given dimension along
+    /// the given boundary, keyed by the generator, tag and diagram of the attachment option.
+    ///
+    /// The index only depends on the signature, so it is kept until the signature changes.
+    fn attach_index(
+        &mut self,
+        dimension: usize,
+        boundary: Boundary,
+        weak_units: bool,
+    ) -> Rc<EmbeddingIndex<AttachKey>> {
+        if let Some(cached) = &self.attach_index {
+            if cached.dimension == dimension
+                && cached.boundary == boundary
+                && cached.weak_units == weak_units
+                && cached.signature == self.proof().signature
+            {
+                return Rc::clone(&cached.index);
+            }
+        }
+
+        let mut index = EmbeddingIndex::default();
+        for info in self.proof().signature.iter() {
+            let mut insert = |diagram: DiagramN, tag: Option<&str>| {
+                let needle = diagram.slice(boundary.flip()).unwrap();
+                index.insert((info.generator, tag.map(str::to_owned), diagram), needle);
+            };
+
+            match info.generator.dimension.cmp(&(dimension + 1)) {
                 std::cmp::Ordering::Less => {
                     if weak_units {
-                        let identity = |mut diagram: Diagram| {
-                            while diagram.dimension() < haystack.dimension() + 1 {
-                                diagram = diagram.weak_identity().into();
-                            }
-                            DiagramN::try_from(diagram).unwrap()
-                        };
-
-                        extend!(identity(info.diagram.clone()), Some("identity".to_owned()));
+                        let mut diagram = info.diagram.clone();
+                        while diagram.dimension() < dimension + 1 {
+                            diagram = diagram.weak_identity().into();
+                        }
+                        insert(DiagramN::try_from(diagram).unwrap(), Some("identity"));
                     }
 
                     if let Diagram::DiagramN(d) = &info.diagram {
                         if info.invertible {
                             let bubble = |mut diagram: DiagramN| {
-                                while diagram.dimension() < haystack.dimension() + 1 {
+                                while diagram.dimension() < dimension + 1 {
                                     diagram = diagram.bubble();
                                 }
                                 diagram
                             };
 
-                            extend!(bubble(d.clone()), Some("bubble".to_owned()));
-                            extend!(bubble(d.inverse()), Some("inverse bubble".to_owned()));
+                            insert(bubble(d.clone()), Some("bubble"));
+                            insert(bubble(d.inverse()), Some("inverse bubble"));
                         }
                     }
                 }
                 std::cmp::Ordering::Equal => {
                     if let Diagram::DiagramN(d) = &info.diagram {
-                        extend!(d.clone(), None);
+                        insert(d.clone(), None);
                         if info.invertible {
-                            extend!(d.inverse(), Some("inverse".to_owned()));
+                            insert(d.inverse(), Some("inverse"));
                         }
                     }
                 }
@@ -358,25 +461,33 @@ impl State {
             }
         }
 
-        match matches.len() {
-            0 => {
-                self.clear_selections();
-                return Err(ModelError::NoAttachment);
-            }
-            1 => {
-                self.clear_selections();
-                self.update(Action::Proof(proof::Action::Attach(
-                    matches.into_iter().next().unwrap(),
-                )))?;
-            }
-            _ => {
-                self.options = Some(Selectables::Attach(matches));
-                self.attachment_highlight = None;
-                self.slice_highlight = None;
-            }
+        let index = Rc::new(index);
+        self.attach_index = Some(AttachIndex {
+            signature: self.proof().signature.clone(),
+            dimension,
+            boundary,
+            weak_units,
+            index: Rc::clone(&index),
+        });
+        index
+    }
+
+    /// Offer every well-typed way of making an ambiguous contraction as options.
+    ///
+    /// Returns whether there were any options to offer.
+    fn contraction_options(&mut self, action: &proof::Action) -> bool {
+        let proof::Action::Homotopy(Homotopy::Contract(contract)) = action else {
+            return false;
+        };
+
+        let options = self.proof().contraction_options(contract);
+        if options.is_empty() {
+            return false;
         }
 
-        Ok(())
+        self.clear_selections();
+        self.options = Some(Selectables::Contract(options));
+        true
     }
 
     /// Handler for [Action::HighlightAttachment].